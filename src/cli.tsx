@@ -15,18 +15,114 @@ const cli = meow(
     $ plaud-tm <command> [options]
 
   Commands
-    update <file>     Update timestamps in transcript
-    merge <patterns>  Merge multiple transcripts
+    update <file>     Update timestamps in transcript (or each in a directory)
+    update <files> --merge  Adjust several segments into one output
+    merge <patterns>  Merge multiple transcripts (files, directories, or globs)
+    rename <patterns> Move transcripts to the canonical name for their timestamps
+    diff <a> <b>      Compare two transcripts line by line by timestamp
+    cat <patterns>    Concatenate files in the order given, without timestamp logic
+
+  Global options:
+    --forward-slashes Print paths with '/' on every platform
+    --temp-dir <dir>  Directory for temporary files during atomic writes
+    --retries <n>     Retry transient write errors n times with backoff
+    --chown <u:g>     Give written files and new directories this owner (Unix)
+    --log-file <file> Also append warnings to file
+    --no-create-dirs  Fail instead of creating a missing output directory
+    --no-fsync        Skip flushing written files to disk, for speed
+    --fsync-dir       Also flush the output directory after each rename
+    --fail-on-warning Exit with status 1 if any warning was produced
 
   Options for update:
-    --time <time>     Start time (HH:MM:SS) [required]
-    --date <date>     Start date (YYYY-MM-DD) [required]
+    --time <time>     Start time (HH:MM:SS or "YYYY-MM-DD HH:MM:SS") [required unless --offset]
+    --date <date>     Start date (YYYY-MM-DD) [required unless --time has a date]
+    --date-input-format <f> Also accept --date as MM/DD/YYYY or DD/MM/YYYY
     --output-dir <dir> Output directory prefix
     --flat            Use flat format
+    --flat-name <n>   Flat name: start-end (default) or start
+    --name <file>     Use this filename, keeping the date directories
+    --output-encoding <e> Write utf-8 (default) or utf-16le with a BOM
+    --format <f>      Write text (default) or jsonl, one JSON object per line
+    --exclusive-dir   Fail if the date directory already has .txt files
+    --preserve-mtime  Set the output's mtime to its last timestamp
+    --layout <l>      Nested directories: day (YYYY/MM/DD, default) or iso-week (YYYY/Www)
+    --fps <n>         Read HH:MM:SS:FF timestamps at this frame rate
+    --dual-timestamps Adjust both start and end timestamps on each line
+    --collapse-speakers Merge consecutive lines from the same speaker
+    --normalize-speaker-case <c> Recase speaker labels: title, upper, or lower
+    --diff            Print a unified diff instead of writing
+    --annotate-original Keep the original time, e.g. 18:01:13 (00:00:01)
+    --min-timestamps <n> Fail if fewer than n lines have timestamps (default 1)
+    --comment-prefix <p> Never adjust lines starting with p, e.g. //
+    --merge           Adjust all given files into one output
+    --merge-base <b>  shared (same start for every file) or chained (default shared)
+    --skip-lines <n>  Copy the first n lines through without adjusting them
+    --max-line-bytes <n> Fail on any line longer than n bytes (default 1048576)
+    --index <file>    Write an index of outputs and their spans
+    --trim            Drop blank lines at the start and end of the output
+    --preview <n>     Also print the first n adjusted lines after writing
+    --allow-empty     Write an empty output for an empty input instead of failing
+    --timestamp-index Also write each timestamp and its line to <output>.idx
+    --checksum        Also write <output>.sha256 for sha256sum -c
+    --offset <dur>    Shift absolute timestamps by [+|-]HH:MM:SS instead of --time
+    --auto-base       Base times on the first timestamp instead of --time
+    --auto-date       Take the date from a YYYYMMDD_HHMMSS_HHMMSS.txt name (overrides --date)
+    --continue-from <f> Start where the updated transcript f ends instead of --time
+    --trace           Print each original -> adjusted timestamp to stderr
+    --round <unit>    Snap timestamps to the nearest unit, e.g. 1s (requires --fps)
+    --ties <policy>   Equal consecutive timestamps: allow (default), warn, or error
+    --ooo-tolerance <d> Only flag timestamps more than HH:MM:SS behind the previous
+    --verify-date <p> warn or error if the output date differs from --date
+    --check           Exit 1 if timestamps are out of order; writes nothing
+    --list-only       Print the detected timestamps without writing
+    --count-only      Print only the number of timestamped lines
+    --speakers-only   Print the distinct speaker labels in order of appearance
+    --since <time>    Drop lines before HH:MM:SS (or +HH:MM:SS into the input)
+    --until <time>    Drop lines from HH:MM:SS on (or +HH:MM:SS into the input)
 
   Options for merge:
     --output <file>   Output file path
+    --output-location <loc> Inferred output in auto, nested, or cwd (default auto)
+    --output-root <dir> Write one output per day under dir/YYYY/MM/DD
+    --base-dir <dir>  Resolve relative patterns and --output against dir
     --no-delete       Keep source files
+    --date-format <fmt> Date format for the inferred output name (default yyyy-MM-dd)
+    --day-dir-format <fmt> Date format of day directory names (e.g. yyyy_MM_dd)
+    --date-header     Start the output with a '# YYYY-MM-DD' line for the segments' date
+    --compress        Gzip the output (implied by an --output ending in .gz)
+    --renumber-speakers Keep speaker numbers from colliding across segments
+    --min-segments <n> Fail if fewer than n segments would be merged (default 1)
+    --expect-delete   Fail if no source files would be deleted
+    --preserve-order  Merge in the order given instead of by time
+    --safe            Refuse to overwrite an output newer than its sources
+    --checksum        Print a SHA-256 of the merged output
+    --verify          Read the output back and check it before deleting sources
+    --hidden          Include dotfiles when matching segments
+    --exclude <glob>  Skip files matching glob (repeatable)
+    --max-depth <n>   Only match files at most n directories below a glob's fixed prefix
+    --start <time>    Name the output <date>_HHMMSS-HHMMSS.txt with this start
+    --end <time>      Name the output <date>_HHMMSS-HHMMSS.txt with this end
+    --group-by-gap <d> Write one output per run of segments with gaps up to d (e.g. 1h)
+    --stream          Stream segments into the output instead of merging in memory
+    --eol <e>         Write every line ending as lf or crlf (default: keep each segment's)
+    --max-output-size <size> Split output into parts of at most this size (e.g. 5M)
+    --json            Print a JSON summary instead of the file list
+    --count-only      Print only the number of segments matched; merges nothing
+    --post-merge-cmd <cmd> Run cmd after merging; {output} is the output path
+
+  Options for rename:
+    --date <date>     Date of the times in the files (YYYY-MM-DD) [required]
+    --date-input-format <f> Also accept --date as MM/DD/YYYY or DD/MM/YYYY
+    --output-dir <dir> Output directory prefix
+    --flat            Use flat format
+    --dry-run         Print the moves without making them
+
+  Options for diff:
+    --tolerance <s>   Pair lines with the same text up to s seconds apart (default 2)
+    --json            Print a JSON report instead of the line list
+
+  Options for cat:
+    --output <file>   Write to file instead of stdout
 
   Examples
     $ plaud-tm update transcript.txt --time 18:06:13 --date 2024-03-15
@@ -35,12 +131,47 @@ const cli = meow(
   {
     importMeta: import.meta,
     flags: {
+      forwardSlashes: {
+        type: 'boolean',
+        default: false,
+      },
+      tempDir: {
+        type: 'string',
+      },
+      retries: {
+        type: 'number',
+      },
+      chown: {
+        type: 'string',
+      },
+      logFile: {
+        type: 'string',
+      },
+      createDirs: {
+        type: 'boolean',
+        default: true,
+      },
+      fsync: {
+        type: 'boolean',
+        default: true,
+      },
+      fsyncDir: {
+        type: 'boolean',
+        default: false,
+      },
+      failOnWarning: {
+        type: 'boolean',
+        default: false,
+      },
       time: {
         type: 'string',
       },
       date: {
         type: 'string',
       },
+      dateInputFormat: {
+        type: 'string',
+      },
       outputDir: {
         type: 'string',
       },
@@ -48,13 +179,241 @@ const cli = meow(
         type: 'boolean',
         default: false,
       },
+      flatName: {
+        type: 'string',
+      },
+      name: {
+        type: 'string',
+      },
+      outputEncoding: {
+        type: 'string',
+      },
+      format: {
+        type: 'string',
+      },
+      exclusiveDir: {
+        type: 'boolean',
+        default: false,
+      },
+      preserveMtime: {
+        type: 'boolean',
+        default: false,
+      },
+      layout: {
+        type: 'string',
+      },
+      fps: {
+        type: 'number',
+      },
+      dualTimestamps: {
+        type: 'boolean',
+        default: false,
+      },
+      collapseSpeakers: {
+        type: 'boolean',
+        default: false,
+      },
+      normalizeSpeakerCase: {
+        type: 'string',
+      },
+      diff: {
+        type: 'boolean',
+        default: false,
+      },
+      annotateOriginal: {
+        type: 'boolean',
+        default: false,
+      },
+      minTimestamps: {
+        type: 'number',
+      },
+      commentPrefix: {
+        type: 'string',
+      },
+      merge: {
+        type: 'boolean',
+        default: false,
+      },
+      mergeBase: {
+        type: 'string',
+      },
+      skipLines: {
+        type: 'number',
+      },
+      maxLineBytes: {
+        type: 'number',
+      },
+      index: {
+        type: 'string',
+      },
+      trim: {
+        type: 'boolean',
+        default: false,
+      },
+      preview: {
+        type: 'number',
+      },
+      allowEmpty: {
+        type: 'boolean',
+        default: false,
+      },
+      timestampIndex: {
+        type: 'boolean',
+        default: false,
+      },
+      offset: {
+        type: 'string',
+      },
+      autoBase: {
+        type: 'boolean',
+        default: false,
+      },
+      autoDate: {
+        type: 'boolean',
+        default: false,
+      },
+      trace: {
+        type: 'boolean',
+        default: false,
+      },
+      continueFrom: {
+        type: 'string',
+      },
+      round: {
+        type: 'string',
+      },
+      ties: {
+        type: 'string',
+      },
+      oooTolerance: {
+        type: 'string',
+      },
+      verifyDate: {
+        type: 'string',
+      },
+      check: {
+        type: 'boolean',
+        default: false,
+      },
+      listOnly: {
+        type: 'boolean',
+        default: false,
+      },
+      speakersOnly: {
+        type: 'boolean',
+        default: false,
+      },
+      countOnly: {
+        type: 'boolean',
+        default: false,
+      },
+      since: {
+        type: 'string',
+      },
+      until: {
+        type: 'string',
+      },
       output: {
         type: 'string',
       },
+      outputLocation: {
+        type: 'string',
+      },
+      outputRoot: {
+        type: 'string',
+      },
+      baseDir: {
+        type: 'string',
+      },
       delete: {
         type: 'boolean',
         default: true,
       },
+      dateFormat: {
+        type: 'string',
+      },
+      dayDirFormat: {
+        type: 'string',
+      },
+      dateHeader: {
+        type: 'boolean',
+        default: false,
+      },
+      compress: {
+        type: 'boolean',
+        default: false,
+      },
+      renumberSpeakers: {
+        type: 'boolean',
+        default: false,
+      },
+      minSegments: {
+        type: 'number',
+      },
+      expectDelete: {
+        type: 'boolean',
+        default: false,
+      },
+      preserveOrder: {
+        type: 'boolean',
+        default: false,
+      },
+      safe: {
+        type: 'boolean',
+        default: false,
+      },
+      checksum: {
+        type: 'boolean',
+        default: false,
+      },
+      verify: {
+        type: 'boolean',
+        default: false,
+      },
+      hidden: {
+        type: 'boolean',
+        default: false,
+      },
+      exclude: {
+        type: 'string',
+        isMultiple: true,
+      },
+      maxDepth: {
+        type: 'number',
+      },
+      start: {
+        type: 'string',
+      },
+      end: {
+        type: 'string',
+      },
+      groupByGap: {
+        type: 'string',
+      },
+      stream: {
+        type: 'boolean',
+        default: false,
+      },
+      eol: {
+        type: 'string',
+      },
+      maxOutputSize: {
+        type: 'string',
+      },
+      json: {
+        type: 'boolean',
+        default: false,
+      },
+      postMergeCmd: {
+        type: 'string',
+      },
+      dryRun: {
+        type: 'boolean',
+        default: false,
+      },
+      tolerance: {
+        type: 'number',
+      },
     },
   }
 );
@@ -0,0 +1,124 @@
+/**
+ * Cat command implementation.
+ */
+
+import { once } from 'events';
+import { createReadStream } from 'fs';
+import { glob } from 'glob';
+import { CatArgs } from '../types.js';
+import { atomicWrite, checkSize } from '../utils/fileio.js';
+import { AppError, CatError, SizeError } from '../errors.js';
+import { MAX_FILE_SIZE } from '../constants.js';
+
+/**
+ * Result of the cat operation.
+ */
+export interface CatOutcome {
+  /** The files concatenated, in order. */
+  files: string[];
+  /** Where the result was written, or undefined for stdout. */
+  outputPath?: string;
+}
+
+/**
+ * Expands the patterns in the order given. The matches of one glob pattern
+ * are taken in name order; nothing is sorted across patterns.
+ * @throws CatError if a pattern matches nothing
+ */
+async function expandPatterns(patterns: string[]): Promise<string[]> {
+  const files: string[] = [];
+  for (const pattern of patterns) {
+    const matches = (await glob(pattern, { nodir: true })).sort();
+    if (matches.length === 0) {
+      throw CatError.noMatches(pattern);
+    }
+    files.push(...matches);
+  }
+  return files;
+}
+
+/** Byte value of a line feed. */
+const LINE_FEED = 0x0a;
+
+/**
+ * Streams the files' bytes one after another, exactly as stored: nothing is
+ * decompressed, decoded, or stripped. A newline is added between files only
+ * when one does not already end with one.
+ * @throws CatError naming the file that could not be read
+ */
+async function* copyFiles(files: string[]): AsyncGenerator<Buffer> {
+  let last = LINE_FEED;
+  for (let i = 0; i < files.length; i++) {
+    if (i > 0 && last !== LINE_FEED) {
+      last = LINE_FEED;
+      yield Buffer.from([LINE_FEED]);
+    }
+    try {
+      for await (const chunk of createReadStream(files[i])) {
+        if (chunk.length > 0) {
+          last = chunk[chunk.length - 1];
+          yield chunk;
+        }
+      }
+    } catch (error: any) {
+      throw CatError.readFailed(files[i], error);
+    }
+  }
+}
+
+/**
+ * Writes streamed chunks to a stream, waiting for it to drain when its buffer fills.
+ */
+async function writeToStream(chunks: AsyncIterable<Buffer>, sink: NodeJS.WritableStream): Promise<void> {
+  for await (const chunk of chunks) {
+    if (!sink.write(chunk)) {
+      await once(sink, 'drain');
+    }
+  }
+}
+
+/**
+ * Executes the cat command: concatenates the matched files in argument order
+ * into the output, or to stdout without one. Unlike merge, no dates are
+ * inferred, nothing is sorted or renumbered, the bytes are copied as they are,
+ * and the sources are never deleted.
+ * @param args Cat command arguments
+ * @param sink Where the result goes without `--output` (default stdout)
+ * @returns The files concatenated and the output path
+ * @throws CatError if a pattern matches nothing, a file is too large, or I/O fails
+ */
+export async function executeCat(args: CatArgs, sink: NodeJS.WritableStream = process.stdout): Promise<CatOutcome> {
+  const files = await expandPatterns(args.patterns);
+
+  // Check file sizes before writing anything
+  for (const file of files) {
+    try {
+      await checkSize(file, MAX_FILE_SIZE);
+    } catch (error: any) {
+      if (error instanceof SizeError) {
+        throw CatError.fileTooLarge(file, error.size, error.max);
+      }
+      throw CatError.ioError(`${file}: ${error.message}`);
+    }
+  }
+
+  const content = copyFiles(files);
+  try {
+    if (args.output) {
+      await atomicWrite(args.output, content, {
+        tempDir: args.tempDir,
+        retries: args.retries,
+        createDirs: !args.noCreateDirs,
+        fsync: !args.noFsync,
+        fsyncDir: args.fsyncDir,
+      });
+    } else {
+      await writeToStream(content, sink);
+    }
+  } catch (error: any) {
+    // Read errors are already reported per file
+    throw error instanceof AppError ? error : CatError.ioError(error.message);
+  }
+
+  return { files, outputPath: args.output };
+}
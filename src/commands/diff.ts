@@ -0,0 +1,157 @@
+/**
+ * Diff command implementation.
+ */
+
+import { DiffArgs } from '../types.js';
+import { TranscriptProcessor, TimedLine } from '../transcript.js';
+import { readFileWithSizeLimit } from '../utils/fileio.js';
+import { DiffError } from '../errors.js';
+import { MAX_FILE_SIZE } from '../constants.js';
+
+/** Default pairing tolerance in seconds. */
+const DEFAULT_TOLERANCE = 2;
+
+/**
+ * Lines with the same text in both transcripts whose timestamps differ.
+ */
+export interface ShiftedLine {
+  /** The line in the first transcript. */
+  a: TimedLine;
+  /** The line in the second transcript. */
+  b: TimedLine;
+}
+
+/**
+ * Result of comparing two transcripts.
+ */
+export interface DiffOutcome {
+  /** Timestamped lines in the first transcript with no counterpart in the second. */
+  onlyInA: TimedLine[];
+  /** Timestamped lines in the second transcript with no counterpart in the first. */
+  onlyInB: TimedLine[];
+  /** Paired lines whose timestamps differ (within the tolerance). */
+  shifted: ShiftedLine[];
+  /** Number of lines paired with identical timestamps. */
+  identical: number;
+}
+
+/**
+ * True if the comparison found any difference.
+ */
+export function hasDifferences(outcome: DiffOutcome): boolean {
+  return outcome.onlyInA.length > 0 || outcome.onlyInB.length > 0 || outcome.shifted.length > 0;
+}
+
+/**
+ * Pairs the timestamped lines of two transcripts. Each line of `a`, in order,
+ * is paired with the unpaired line of `b` that has the same text and the
+ * nearest timestamp, provided the two are at most `toleranceMs` apart; on a
+ * tie the earlier line of `b` wins. Lines left unpaired on either side are
+ * reported as missing from the other. Lines without a timestamp are ignored.
+ * @param a Timestamped lines of the first transcript
+ * @param b Timestamped lines of the second transcript
+ * @param toleranceMs Largest timestamp difference at which lines are paired
+ * @returns The differences between the two
+ */
+export function alignTranscripts(a: TimedLine[], b: TimedLine[], toleranceMs: number): DiffOutcome {
+  const paired = new Set<number>();
+  const onlyInA: TimedLine[] = [];
+  const shifted: ShiftedLine[] = [];
+  let identical = 0;
+
+  for (const line of a) {
+    let best = -1;
+    let bestDistance = Infinity;
+    b.forEach((candidate, index) => {
+      const distance = Math.abs(candidate.time.getTime() - line.time.getTime());
+      if (!paired.has(index) && candidate.text === line.text && distance <= toleranceMs && distance < bestDistance) {
+        best = index;
+        bestDistance = distance;
+      }
+    });
+
+    if (best === -1) {
+      onlyInA.push(line);
+    } else {
+      paired.add(best);
+      if (bestDistance === 0) {
+        identical++;
+      } else {
+        shifted.push({ a: line, b: b[best] });
+      }
+    }
+  }
+
+  const onlyInB = b.filter((_, index) => !paired.has(index));
+  return { onlyInA, onlyInB, shifted, identical };
+}
+
+/**
+ * Reads a transcript for comparison.
+ */
+async function readTimeline(path: string): Promise<TimedLine[]> {
+  try {
+    return TranscriptProcessor.timeline(await readFileWithSizeLimit(path, MAX_FILE_SIZE));
+  } catch (error: any) {
+    throw DiffError.ioError(`${path}: ${error.message}`);
+  }
+}
+
+/**
+ * Executes the diff operation: compares two transcripts by their timestamped
+ * lines. Timestamps are compared as written, so both files should already be
+ * adjusted (or both unadjusted).
+ * @param args Diff arguments
+ * @returns The differences between the two transcripts
+ */
+export async function executeDiff(args: DiffArgs): Promise<DiffOutcome> {
+  const tolerance = args.tolerance ?? DEFAULT_TOLERANCE;
+  if (!Number.isInteger(tolerance) || tolerance < 0) {
+    throw DiffError.invalidTolerance(tolerance);
+  }
+
+  const [a, b] = await Promise.all([readTimeline(args.a), readTimeline(args.b)]);
+  return alignTranscripts(a, b, tolerance * 1000);
+}
+
+/**
+ * Renders the human-readable report: one line per difference, in timestamp
+ * order. `-` marks a line only in the first transcript, `+` one only in the
+ * second, and `~` a line whose timestamp moved.
+ */
+export function formatDiffReport(outcome: DiffOutcome): string[] {
+  const entries: { time: number; line: string }[] = [
+    ...outcome.onlyInA.map((line) => ({
+      time: line.time.getTime(),
+      line: `- ${line.timestamp} ${line.text} (a:${line.lineNumber})`,
+    })),
+    ...outcome.onlyInB.map((line) => ({
+      time: line.time.getTime(),
+      line: `+ ${line.timestamp} ${line.text} (b:${line.lineNumber})`,
+    })),
+    ...outcome.shifted.map(({ a, b }) => ({
+      time: a.time.getTime(),
+      line: `~ ${a.timestamp} -> ${b.timestamp} ${a.text} (a:${a.lineNumber}, b:${b.lineNumber})`,
+    })),
+  ];
+
+  // Stable sort keeps removals before additions at the same time
+  return entries.sort((x, y) => x.time - y.time).map(({ line }) => line);
+}
+
+/**
+ * Renders the JSON report printed by `diff --json`.
+ */
+export function formatDiffJson(outcome: DiffOutcome): string {
+  const describe = ({ lineNumber, timestamp, text }: TimedLine) => ({ line: lineNumber, timestamp, text });
+  return JSON.stringify(
+    {
+      onlyInA: outcome.onlyInA.map(describe),
+      onlyInB: outcome.onlyInB.map(describe),
+      shifted: outcome.shifted.map(({ a, b }) => ({ a: describe(a), b: describe(b) })),
+      identical: outcome.identical,
+    },
+    null,
+    2
+  );
+}
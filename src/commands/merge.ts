@@ -2,14 +2,42 @@
  * Merge command implementation.
  */
 
-import { promises as fs } from 'fs';
-import { join, dirname, basename } from 'path';
+import { promises as fs, createReadStream, Stats } from 'fs';
+import { join, dirname, basename, extname, isAbsolute, resolve, relative, sep } from 'path';
 import { parse, format } from 'date-fns';
-import { glob } from 'glob';
+import { glob, escape } from 'glob';
+import { createHash } from 'crypto';
+import { spawn } from 'child_process';
+import { gunzipSync } from 'zlib';
 import { MergeArgs } from '../types.js';
-import { atomicWrite } from '../utils/fileio.js';
-import { MergeError } from '../errors.js';
-import { MAX_FILE_SIZE, DATE_FORMAT_COMPACT, DATE_FORMAT_DASHED } from '../constants.js';
+import {
+  atomicWrite,
+  checkSize,
+  encodeContent,
+  fileStartsWith,
+  isDirectory,
+  isSpecialFile,
+  AtomicWriteOptions,
+} from '../utils/fileio.js';
+import { validateDateFormat, validateSize, validateDuration, validateTime } from '../utils/validation.js';
+import { resolveOwner, Owner } from '../utils/ownership.js';
+import { MergeError, SizeError } from '../errors.js';
+import { Warning, appendWarningLog } from '../warnings.js';
+import {
+  MAX_FILE_SIZE,
+  DATE_FORMAT_COMPACT,
+  DATE_FORMAT_DASHED,
+  DATETIME_FORMAT,
+  TIME_FORMAT,
+  TIME_FORMAT_COMPACT,
+  INDEX_HEADER,
+  SEGMENT_EXTENSION_REGEX,
+  SPEAKER_LABEL_REGEX,
+  GZIP_EXTENSION,
+  YEAR_FORMAT,
+  MONTH_FORMAT,
+  DAY_FORMAT,
+} from '../constants.js';
 
 /**
  * Result of the merge operation.
@@ -19,6 +47,35 @@ export interface MergeOutcome {
   files: string[];
   /** Path where the merged output was written. */
   outputPath: string;
+  /** Files written: just `outputPath`, or its numbered parts when split by size. */
+  parts: string[];
+  /** Number of segments merged. */
+  segments: number;
+  /** Number of lines in the merged content. */
+  lines: number;
+  /** Non-fatal conditions encountered while merging. */
+  warnings: Warning[];
+  /** Integrity information, present when requested with `checksum`. */
+  checksum?: MergeChecksum;
+  /** True if the merged sources were deleted. */
+  deleted: boolean;
+  /** Span covered by the merged segments, or null if nothing was merged. */
+  span: MergeSpan | null;
+  /** Exit status of the `postMergeCmd` command, present when one was run. */
+  postMergeStatus?: number;
+}
+
+/**
+ * Integrity information for a merged output.
+ */
+export interface MergeChecksum {
+  /**
+   * Hex SHA-256 of the exact bytes of each written file (compressed for a gzip
+   * output), in the order of the outcome's `parts`, so `sha256sum` can check them.
+   */
+  sha256: string[];
+  /** Total size in bytes of the merged source files. */
+  sourceBytes: number;
 }
 
 /**
@@ -29,6 +86,34 @@ interface FileSortKey {
   date: Date | null;
   /** Start time extracted from filename. */
   start: Date;
+  /** End time extracted from filename, or null if it is not a valid time. */
+  end: Date | null;
+}
+
+/**
+ * A recognized transcript segment name and what it encodes.
+ */
+export interface FileKind {
+  /** `flat` for YYYYMMDD_HHMMSS_HHMMSS.txt, `nested` for HHMMSS-HHMMSS.txt. */
+  format: 'flat' | 'nested';
+  /** Date from the flat name or the nested day directory; null if a nested file is outside one. */
+  date: Date | null;
+  /** Start time from the name. */
+  start: Date;
+  /** End time from the name, or null if it is not a valid time. */
+  end: Date | null;
+}
+
+/**
+ * Time span covered by the merged segments, taken from their filenames.
+ */
+export interface MergeSpan {
+  /** Start of the first segment. */
+  start: Date;
+  /** End of the last segment. */
+  end: Date;
+  /** True if the segments' date is known, so `start`/`end` carry it. */
+  hasDate: boolean;
 }
 
 /**
@@ -109,11 +194,23 @@ function looksLikeNestedFormat(filename: string): boolean {
 
 /**
  * Extracts date from directory structure (YYYY/MM/DD or single YYYY-MM-DD).
+ * A `dayDirFormat` (date-fns) for single-directory names is tried first.
  */
-function extractNestedDayDirectory(path: string): { dir: string; date: Date } | null {
+function extractNestedDayDirectory(path: string, dayDirFormat?: string): { dir: string; date: Date } | null {
   const dayDir = dirname(path);
   const dayName = basename(dayDir);
 
+  if (dayDirFormat !== undefined) {
+    try {
+      const date = parse(dayName, dayDirFormat, new Date());
+      if (!isNaN(date.getTime())) {
+        return { dir: dayDir, date };
+      }
+    } catch {
+      // Fall back to the built-in shapes
+    }
+  }
+
   // Try parsing as YYYY-MM-DD
   try {
     const date = parse(dayName, DATE_FORMAT_DASHED, new Date());
@@ -163,6 +260,7 @@ function parseFlatFormat(filename: string): FileSortKey | null {
 
   const datePart = parts[0];
   const startPart = parts[1];
+  const endPart = parts[2];
 
   try {
     const date = parse(datePart, DATE_FORMAT_COMPACT, new Date());
@@ -175,7 +273,7 @@ function parseFlatFormat(filename: string): FileSortKey | null {
       return null;
     }
 
-    return { date, start };
+    return { date, start, end: parseTimeDigits(endPart) };
   } catch {
     return null;
   }
@@ -184,7 +282,7 @@ function parseFlatFormat(filename: string): FileSortKey | null {
 /**
  * Parses a nested format filename: HHMMSS-HHMMSS
  */
-function parseNestedFormat(path: string, filename: string): FileSortKey | null {
+function parseNestedFormat(path: string, filename: string, dayDirFormat?: string): FileSortKey | null {
   const parts = filename.split('-');
   if (parts.length !== 2) {
     return null;
@@ -196,45 +294,148 @@ function parseNestedFormat(path: string, filename: string): FileSortKey | null {
     return null;
   }
 
-  const dirInfo = extractNestedDayDirectory(path);
+  const dirInfo = extractNestedDayDirectory(path, dayDirFormat);
   const date = dirInfo ? dirInfo.date : null;
 
-  return { date, start };
+  return { date, start, end: parseTimeDigits(parts[1]) };
 }
 
 /**
- * Extracts sort key from a file path.
+ * Reduces a segment path to the name its format is read from. Sync tools
+ * sometimes leave a leading dot (.061901-111901.txt), and exports may add a
+ * single tag before the extension (061901-111901.v2.txt); both are ignored,
+ * as is a `.gz` extension on a compressed segment. Only `.txt` and `.txt.gz`
+ * names lose their extension, so sidecars such as `180113-180115.idx` or a
+ * `.jsonl` output never pass for segments.
  */
-function extractSortKey(path: string): FileSortKey {
-  const filename = basename(path, '.txt');
+function segmentStem(path: string): string {
+  const name = basename(path).replace(/^\./, '');
+  if (!SEGMENT_EXTENSION_REGEX.test(name)) {
+    return name;
+  }
+  return name.replace(SEGMENT_EXTENSION_REGEX, '').replace(/\.[A-Za-z0-9_-]+$/, '');
+}
+
+/**
+ * Classifies a path as a Plaud transcript segment name and reads the date and
+ * times it encodes, without touching the filesystem.
+ * @param path The segment path; for nested names the day directory supplies the date
+ * @param dayDirFormat date-fns format of single-directory day names, tried before the built-in shapes
+ * @returns The format and encoded times, or null if the name is not recognized
+ */
+export function classifyFilename(path: string, dayDirFormat?: string): FileKind | null {
+  const filename = segmentStem(path);
 
   // Try flat format first
   if (looksLikeFlatFormat(filename)) {
     const key = parseFlatFormat(filename);
     if (key) {
-      return key;
+      return { format: 'flat', ...key };
     }
   }
 
   // Try nested format
   if (looksLikeNestedFormat(filename)) {
-    const key = parseNestedFormat(path, filename);
+    const key = parseNestedFormat(path, filename, dayDirFormat);
     if (key) {
-      return key;
+      return { format: 'nested', ...key };
     }
   }
 
-  throw MergeError.unrecognizedFilename(filename);
+  return null;
+}
+
+/**
+ * Extracts sort key from a file path.
+ */
+function extractSortKey(path: string, dayDirFormat?: string): FileSortKey {
+  const kind = classifyFilename(path, dayDirFormat);
+  if (!kind) {
+    throw MergeError.unrecognizedFilename(segmentStem(path));
+  }
+  return { date: kind.date, start: kind.start, end: kind.end };
+}
+
+/**
+ * Checks whether a filename is a recognized transcript segment name.
+ */
+function isRecognizedFilename(path: string): boolean {
+  return classifyFilename(path) !== null;
+}
+
+/**
+ * Lists the recognized transcript segments directly inside a directory.
+ * Dotfiles are included only when `hidden` is set, matching glob behavior.
+ */
+async function expandDirectory(dir: string, hidden: boolean): Promise<string[]> {
+  const entries = await fs.readdir(dir, { withFileTypes: true });
+  return entries
+    .filter((entry) => !entry.isDirectory() && SEGMENT_EXTENSION_REGEX.test(entry.name))
+    .filter((entry) => hidden || !entry.name.startsWith('.'))
+    .map((entry) => join(dir, entry.name))
+    .filter(isRecognizedFilename)
+    .sort();
+}
+
+/**
+ * Gives nested segments outside a recognizable day directory the date of the
+ * other segments in the batch, when all of those agree on a single date. With
+ * conflicting dates the keys are left alone and output naming reports them.
+ */
+function borrowSharedDate(keys: FileSortKey[]): void {
+  let shared: Date | null = null;
+  for (const { date } of keys) {
+    if (date) {
+      if (shared && shared.getTime() !== date.getTime()) {
+        return;
+      }
+      shared = date;
+    }
+  }
+
+  if (shared) {
+    for (const key of keys) {
+      key.date ??= shared;
+    }
+  }
+}
+
+/**
+ * The literal directory a glob pattern starts from: its leading components up
+ * to the first one containing a wildcard, so `2024/03/**` starts from `2024/03`.
+ */
+function globRoot(pattern: string): string {
+  const components = pattern.split('/');
+  const literal: string[] = [];
+  for (const component of components.slice(0, -1)) {
+    if (/[*?[\]{}()!+@]/.test(component)) {
+      break;
+    }
+    literal.push(component);
+  }
+  return literal.length > 0 ? literal.join('/') || '/' : '.';
+}
+
+/**
+ * Keeps glob matches at most `maxDepth` directories below the pattern's
+ * literal root; 0 keeps only files directly inside it.
+ */
+function withinDepth(matches: string[], pattern: string, maxDepth: number | undefined): string[] {
+  if (maxDepth === undefined) {
+    return matches;
+  }
+  const root = resolve(globRoot(pattern));
+  return matches.filter((match) => relative(root, resolve(match)).split(sep).length - 1 <= maxDepth);
 }
 
 /**
  * Detects common nested directory among files.
  */
-function detectCommonNestedDirectory(paths: string[]): { dir: string; date: Date } | null {
+function detectCommonNestedDirectory(paths: string[], dayDirFormat?: string): { dir: string; date: Date } | null {
   let candidate: { dir: string; date: Date } | null = null;
 
   for (const path of paths) {
-    const info = extractNestedDayDirectory(path);
+    const info = extractNestedDayDirectory(path, dayDirFormat);
     if (!info) {
       return null;
     }
@@ -251,128 +452,787 @@ function detectCommonNestedDirectory(paths: string[]): { dir: string; date: Date
   return candidate;
 }
 
+/**
+ * The one date shared by every segment that has a date.
+ * @throws MergeError if the dates differ or no segment has one
+ */
+function sharedDate(descriptors: Array<{ path: string; key: FileSortKey }>): Date {
+  let selectedDate: Date | null = null;
+  for (const { key } of descriptors) {
+    if (key.date) {
+      if (selectedDate) {
+        if (selectedDate.getTime() !== key.date.getTime()) {
+          throw MergeError.mixedDates();
+        }
+      } else {
+        selectedDate = key.date;
+      }
+    }
+  }
+
+  if (!selectedDate) {
+    throw MergeError.undeterminedDate();
+  }
+  return selectedDate;
+}
+
 /**
  * Determines the output path for merged file.
+ *
+ * With the `auto` location, files that all sit in one nested day directory
+ * merge into it; otherwise the output goes next to the first file in order.
+ * `nested` always uses the day directory of the nested segments, and `cwd`
+ * the current directory. An output root replaces the location: the output
+ * goes in the `YYYY/MM/DD` directory for the segments' date under it.
  */
 function determineOutputPath(
   ordered: string[],
   descriptors: Array<{ path: string; key: FileSortKey }>,
-  args: MergeArgs
+  args: MergeArgs,
+  nameSuffix = ''
 ): string {
   if (args.output) {
-    return args.output;
+    return args.baseDir && !isAbsolute(args.output) ? join(args.baseDir, args.output) : args.output;
+  }
+
+  const dateFormat = args.dateFormat ?? DATE_FORMAT_DASHED;
+  const location = args.outputLocation ?? 'auto';
+
+  if (args.outputRoot !== undefined) {
+    const date = sharedDate(descriptors);
+    const root = args.baseDir && !isAbsolute(args.outputRoot) ? join(args.baseDir, args.outputRoot) : args.outputRoot;
+    const dayDir = join(root, format(date, YEAR_FORMAT), format(date, MONTH_FORMAT), format(date, DAY_FORMAT));
+    return join(dayDir, format(date, dateFormat) + nameSuffix + '.txt');
   }
 
   // Check if all files share a common nested directory
-  const commonDir = detectCommonNestedDirectory(ordered);
+  const commonDir = location === 'auto' ? detectCommonNestedDirectory(ordered, args.dayDirFormat) : null;
   if (commonDir) {
-    const filename = format(commonDir.date, DATE_FORMAT_DASHED) + '.txt';
+    const filename = format(commonDir.date, dateFormat) + nameSuffix + '.txt';
     return join(commonDir.dir, filename);
   }
 
   // Check if all files have the same date in flat format
-  let selectedDate: Date | null = null;
-  for (const { key } of descriptors) {
-    if (key.date) {
-      if (selectedDate) {
-        if (selectedDate.getTime() !== key.date.getTime()) {
-          throw MergeError.mixedDates();
-        }
-      } else {
-        selectedDate = key.date;
+  const selectedDate = sharedDate(descriptors);
+  const filename = format(selectedDate, dateFormat) + nameSuffix + '.txt';
+
+  if (location === 'nested') {
+    // The nested segments must all live in one day directory
+    const dirs = new Set<string>();
+    for (const path of ordered) {
+      const info = extractNestedDayDirectory(path, args.dayDirFormat);
+      if (info) {
+        dirs.add(info.dir);
       }
     }
+    if (dirs.size !== 1) {
+      throw MergeError.noNestedDirectory(dirs.size);
+    }
+    return join([...dirs][0], filename);
   }
 
-  if (selectedDate) {
-    const baseDir = ordered.length > 0 ? dirname(ordered[0]) : '.';
-    const filename = format(selectedDate, DATE_FORMAT_DASHED) + '.txt';
-    return join(baseDir, filename);
+  if (location === 'cwd') {
+    // --base-dir stands in for the current directory
+    return join(args.baseDir ? resolve(args.baseDir) : process.cwd(), filename);
   }
 
-  throw MergeError.undeterminedDate();
+  const baseDir = ordered.length > 0 ? dirname(ordered[0]) : '.';
+  return join(baseDir, filename);
 }
 
 /**
- * Writes merged content to output file.
+ * Places a filename time on the segment's date, if known.
  */
-async function writeMergedFile(files: string[], outputPath: string): Promise<void> {
-  let merged = '';
+function atDate(time: Date, date: Date | null): Date {
+  const result = new Date(date ?? time);
+  result.setHours(time.getHours(), time.getMinutes(), time.getSeconds(), 0);
+  return result;
+}
+
+/**
+ * Computes the span from the first source's start to the last source's end.
+ * A last segment without a valid end time ends where it starts.
+ */
+function computeSpan(sources: string[], keys: Map<string, FileSortKey>): MergeSpan | null {
+  if (sources.length === 0) {
+    return null;
+  }
+
+  const first = keys.get(sources[0])!;
+  const last = keys.get(sources[sources.length - 1])!;
+  return {
+    start: atDate(first.start, first.date),
+    end: atDate(last.end ?? last.start, last.date),
+    hasDate: first.date !== null && last.date !== null,
+  };
+}
+
+/**
+ * Renders a merge outcome as the JSON summary printed by `merge --json`, or
+ * an array of summaries for the outcomes of `--group-by-gap`.
+ * Span times are `YYYY-MM-DD HH:MM:SS` when the date is known, else `HH:MM:SS`.
+ * `parts` lists the files actually written, which differ from `output` when
+ * the output was split; `sha256` (one per part) is only present with `--checksum`.
+ */
+export function formatMergeJson(outcome: MergeOutcome | MergeOutcome[]): string {
+  const summarize = ({ files, outputPath, parts, deleted, span, checksum, warnings }: MergeOutcome) => {
+    const spanFormat = span?.hasDate ? DATETIME_FORMAT : TIME_FORMAT;
+    return {
+      sources: files,
+      output: outputPath,
+      parts,
+      deleted,
+      span: span ? { start: format(span.start, spanFormat), end: format(span.end, spanFormat) } : null,
+      ...(checksum ? { sha256: checksum.sha256 } : {}),
+      warnings: warnings.map(({ kind, message }) => ({ kind, message })),
+    };
+  };
+  return JSON.stringify(Array.isArray(outcome) ? outcome.map(summarize) : summarize(outcome), null, 2);
+}
+
+/**
+ * Quotes a path as a single shell word, so spaces and metacharacters in it are
+ * never interpreted by the shell.
+ */
+function shellQuote(path: string): string {
+  if (process.platform === 'win32') {
+    return `"${path.replace(/"/g, '""')}"`;
+  }
+  return `'${path.replace(/'/g, `'\\''`)}'`;
+}
 
+/**
+ * Runs the post-merge command through the shell with `{output}` replaced by the
+ * quoted output path(s), forwarding its output to ours.
+ * @returns The command's exit status (1 if it was killed by a signal)
+ */
+async function runPostMergeCommand(command: string, outputs: string[]): Promise<number> {
+  const line = command.replace(/\{output\}/g, () => outputs.map(shellQuote).join(' '));
+  return new Promise((resolvePromise, reject) => {
+    const child = spawn(line, { shell: true, stdio: ['ignore', 'inherit', 'inherit'] });
+    child.on('error', (error) => reject(MergeError.postMergeFailed(command, error.message)));
+    child.on('close', (code) => resolvePromise(code ?? 1));
+  });
+}
+
+/**
+ * Resolves a path to its canonical absolute form, following symlinks.
+ * Paths that do not exist yet resolve against their canonical parent directory.
+ */
+async function canonicalize(path: string): Promise<string> {
+  try {
+    return await fs.realpath(path);
+  } catch {
+    try {
+      return join(await fs.realpath(dirname(path)), basename(path));
+    } catch {
+      return resolve(path);
+    }
+  }
+}
+
+/**
+ * Checks whether the output already exists and was modified after every source,
+ * which suggests it was edited by hand since the last merge. Source times come
+ * from the metadata gathered during the size check.
+ */
+async function isOutputNewer(
+  outputPath: string,
+  sources: string[],
+  metadata: Map<string, Stats>
+): Promise<boolean> {
+  let outputTime: number;
+  try {
+    outputTime = (await fs.stat(outputPath)).mtimeMs;
+  } catch {
+    return false;
+  }
+
+  for (const source of sources) {
+    const stats = metadata.get(source) ?? (await fs.stat(source));
+    if (stats.mtimeMs >= outputTime) {
+      return false;
+    }
+  }
+  return true;
+}
+
+/**
+ * Reads a segment as UTF-8, failing with the file's name on invalid bytes.
+ * A `.gz` segment is decompressed first, with the size limit applied to the
+ * decompressed content. A leading byte order mark is kept only on the first
+ * segment, so none ends up in the middle of the merged output.
+ */
+async function readSegment(file: string, first: boolean): Promise<string> {
+  let bytes = await fs.readFile(file);
+  if (file.endsWith(GZIP_EXTENSION)) {
+    try {
+      bytes = gunzipSync(bytes, { maxOutputLength: MAX_FILE_SIZE });
+    } catch (error: any) {
+      if (error.code === 'ERR_BUFFER_TOO_LARGE') {
+        throw MergeError.decompressedTooLarge(file, MAX_FILE_SIZE);
+      }
+      throw MergeError.ioError(`${file}: ${error.message}`);
+    }
+  }
+  let text: string;
+  try {
+    text = new TextDecoder('utf-8', { fatal: true, ignoreBOM: true }).decode(bytes);
+  } catch {
+    throw MergeError.invalidUtf8(file);
+  }
+  return !first && text.startsWith('\uFEFF') ? text.substring(1) : text;
+}
+
+/**
+ * Offsets `Speaker N` labels so each segment's speakers follow on from the
+ * previous segments' highest number: if the first segment has Speaker 1 and 2,
+ * the second segment's Speaker 1 becomes Speaker 3. Numbers are only shifted
+ * apart; nobody is matched across segments.
+ * @param segments Segment contents, rewritten in place
+ */
+function renumberSpeakers(segments: string[]): void {
+  let offset = 0;
+  for (let i = 0; i < segments.length; i++) {
+    const renumbered = renumberSegment(segments[i], offset);
+    segments[i] = renumbered.text;
+    offset += renumbered.highest;
+  }
+}
+
+/**
+ * Adds an offset to the `Speaker N` labels of one segment.
+ * @returns The rewritten segment and the highest number it used before the offset
+ */
+function renumberSegment(segment: string, offset: number): { text: string; highest: number } {
+  let highest = 0;
+  const text = segment.replace(SPEAKER_LABEL_REGEX, (_match, digits: string) => {
+    const number = parseInt(digits, 10);
+    highest = Math.max(highest, number);
+    return `Speaker ${number + offset}`;
+  });
+  return { text, highest };
+}
+
+/**
+ * Prefixes the first segment with the `--date-header` line and a blank line,
+ * unless it already starts with that line. The header takes the segment's line endings.
+ */
+function withHeader(segment: string, header: string | undefined): string {
+  if (header === undefined || segment.split('\n', 1)[0].trimEnd() === header) {
+    return segment;
+  }
+  const eol = dominantEol(segment);
+  return `${header}${eol}${eol}${segment}`;
+}
+
+/**
+ * The line ending a segment mostly uses: CRLF when most of its line breaks
+ * are CRLF, else LF (also for a segment without any).
+ */
+function dominantEol(segment: string): '\n' | '\r\n' {
+  let crlf = 0;
+  let lf = 0;
+  for (let position = segment.indexOf('\n'); position !== -1; position = segment.indexOf('\n', position + 1)) {
+    if (position > 0 && segment.charAt(position - 1) === '\r') {
+      crlf++;
+    } else {
+      lf++;
+    }
+  }
+  return crlf > lf ? '\r\n' : '\n';
+}
+
+/**
+ * Rewrites every line ending of a segment as `eol` (`--eol`); without one the
+ * segment keeps its own.
+ */
+function withEol(segment: string, eol: '\n' | '\r\n' | undefined): string {
+  return eol === undefined ? segment : segment.replace(/\r?\n/g, eol);
+}
+
+/**
+ * Reads and yields the segments one at a time, with the same renumbering,
+ * header, and separating newlines as the buffered merge, so only one segment
+ * is held in memory at once.
+ */
+async function* streamSegments(files: string[], content: MergeContentOptions): AsyncGenerator<string> {
+  let offset = 0;
+  let last = '';
   for (let i = 0; i < files.length; i++) {
-    const segment = await fs.readFile(files[i], 'utf-8');
-    merged += segment;
+    let segment = withEol(await readSegment(files[i], i === 0), content.eol);
+    if (content.renumber) {
+      const renumbered = renumberSegment(segment, offset);
+      segment = renumbered.text;
+      offset += renumbered.highest;
+    }
+    if (i === 0) {
+      segment = withHeader(segment, content.header);
+    }
+    if (segment.length > 0) {
+      last = segment.charAt(segment.length - 1);
+      yield segment;
+    }
+
+    // Add newline between segments if the content so far does not end with one
+    if (i + 1 < files.length && last !== '\n') {
+      last = '\n';
+      yield dominantEol(segment);
+    }
+  }
+}
+
+/**
+ * Line count and SHA-256 of streamed content, taken as it passes through.
+ */
+class ContentTally {
+  private readonly hash = createHash('sha256');
+  private newlines = 0;
+  private last = '';
+
+  /** Passes the chunks through, tallying each one. */
+  async *track(chunks: AsyncIterable<string>): AsyncGenerator<string> {
+    for await (const chunk of chunks) {
+      this.hash.update(chunk, 'utf-8');
+      for (let position = chunk.indexOf('\n'); position !== -1; position = chunk.indexOf('\n', position + 1)) {
+        this.newlines++;
+      }
+      if (chunk.length > 0) {
+        this.last = chunk.charAt(chunk.length - 1);
+      }
+      yield chunk;
+    }
+  }
+
+  /** Lines seen, counted as {@link countLines} would. */
+  get lines(): number {
+    return this.last !== '' && this.last !== '\n' ? this.newlines + 1 : this.newlines;
+  }
+
+  /** Hex SHA-256 of everything seen. */
+  digest(): string {
+    return this.hash.digest('hex');
+  }
+}
+
+/**
+ * Counts the lines in some text; a final line without a newline still counts.
+ */
+export function countLines(text: string): number {
+  let lines = 0;
+  for (let position = text.indexOf('\n'); position !== -1; position = text.indexOf('\n', position + 1)) {
+    lines++;
+  }
+  return text.length > 0 && !text.endsWith('\n') ? lines + 1 : lines;
+}
+
+/**
+ * Joins segments, adding a newline between them if not already present. The
+ * added newline matches the line endings of the segment it ends, so CRLF and
+ * LF segments each keep their own.
+ * @param segments The segment contents in order
+ * @returns The merged content
+ */
+export function joinSegments(segments: string[]): string {
+  let merged = '';
+
+  for (let i = 0; i < segments.length; i++) {
+    merged += segments[i];
 
     // Add newline between segments if not already present
-    if (i + 1 < files.length && !merged.endsWith('\n')) {
-      merged += '\n';
+    if (i + 1 < segments.length && !merged.endsWith('\n')) {
+      merged += dominantEol(segments[i]);
     }
   }
 
-  await atomicWrite(outputPath, merged);
+  return merged;
 }
 
 /**
- * Deletes source files after merging.
+ * Groups segments into parts of at most `maxBytes` each, splitting only at
+ * segment boundaries. A single segment larger than the limit gets its own part.
+ */
+function planParts(segments: string[], maxBytes: number): string[][] {
+  const parts: string[][] = [];
+  let current: string[] = [];
+  let currentBytes = 0;
+  let endsWithNewline = false;
+  let separator = '\n';
+
+  for (const segment of segments) {
+    const segmentBytes = Buffer.byteLength(segment, 'utf-8');
+    let separatorBytes = current.length > 0 && !endsWithNewline ? separator.length : 0;
+
+    if (current.length > 0 && currentBytes + separatorBytes + segmentBytes > maxBytes) {
+      parts.push(current);
+      current = [];
+      currentBytes = 0;
+      separatorBytes = 0;
+    }
+
+    currentBytes += separatorBytes + segmentBytes;
+    current.push(segment);
+    separator = dominantEol(segment);
+    // An empty segment leaves the previous separator as the trailing newline
+    endsWithNewline = segment.endsWith('\n') || (segment.length === 0 && current.length > 1);
+  }
+
+  if (current.length > 0) {
+    parts.push(current);
+  }
+  return parts;
+}
+
+/**
+ * Derives the path of a numbered part: 2025-01-27.txt becomes 2025-01-27.part1.txt,
+ * and 2025-01-27.txt.gz becomes 2025-01-27.part1.txt.gz.
+ */
+function partPath(outputPath: string, index: number): string {
+  const gz = outputPath.endsWith(GZIP_EXTENSION) ? GZIP_EXTENSION : '';
+  const plain = outputPath.substring(0, outputPath.length - gz.length);
+  const extension = extname(plain) + gz;
+  const stem = outputPath.substring(0, outputPath.length - extension.length);
+  return `${stem}.part${index}${extension}`;
+}
+
+/**
+ * The `# YYYY-MM-DD` header line for `--date-header`, from the one date the
+ * segments share.
+ * @throws MergeError if the segments have no date or more than one
  */
-async function deleteSources(files: string[], outputPath: string): Promise<void> {
-  // Canonicalize output path for reliable comparison
-  let outputCanonical: string;
+function dateHeader(files: string[], keys: Map<string, FileSortKey>): string {
+  const dates = new Set<string>();
+  for (const file of files) {
+    const date = keys.get(file)?.date;
+    if (date) {
+      dates.add(format(date, DATE_FORMAT_DASHED));
+    }
+  }
+  if (dates.size !== 1) {
+    throw MergeError.noHeaderDate(dates.size);
+  }
+  return `# ${[...dates][0]}`;
+}
+
+/**
+ * How the merged content is put together and written.
+ */
+interface MergeContentOptions {
+  /** Split into numbered parts of at most this many bytes. */
+  maxBytes?: number;
+  /** Offset speaker numbers so they do not collide across segments. */
+  renumber?: boolean;
+  /** Line to start the output with, followed by a blank line. */
+  header?: string;
+  /** Stream the segments into the output one at a time instead of joining them in memory. */
+  stream?: boolean;
+  /** Line ending to rewrite every segment to, instead of keeping each segment's own. */
+  eol?: '\n' | '\r\n';
+  /** Read each written file back and check it holds exactly the merged content. */
+  verify?: boolean;
+  /** Hash the written bytes even where that means reading the output back (a streamed gzip output). */
+  checksum?: boolean;
+}
+
+/**
+ * Reads a written output back for `--verify` and checks that it holds exactly
+ * the merged content, so a write corrupted on its way to disk is caught before
+ * any source is deleted. A gzip output is decompressed first.
+ * @param expectedSha256 Hex SHA-256 of the merged content that was written
+ * @throws MergeError if the output cannot be read back or differs
+ */
+async function verifyOutput(path: string, expectedSha256: string, gzip: boolean | undefined): Promise<void> {
+  let bytes: Buffer;
   try {
-    outputCanonical = await fs.realpath(outputPath);
-  } catch {
-    outputCanonical = outputPath;
+    bytes = await fs.readFile(path);
+    if (gzip) {
+      bytes = gunzipSync(bytes);
+    }
+  } catch (error: any) {
+    throw MergeError.verificationFailed(path, error.message);
+  }
+  if (sha256Hex(bytes) !== expectedSha256) {
+    throw MergeError.verificationFailed(path, 'its content differs from the merged segments');
   }
+}
 
-  for (const path of files) {
-    // Canonicalize file path
-    let pathCanonical: string;
-    try {
-      pathCanonical = await fs.realpath(path);
-    } catch {
-      pathCanonical = path;
+/** Hex SHA-256 of text (as UTF-8) or bytes. */
+function sha256Hex(data: string | Buffer): string {
+  return createHash('sha256').update(data).digest('hex');
+}
+
+/**
+ * Hashes a written file from disk a chunk at a time, for a streamed gzip
+ * output whose compressed bytes are never held in memory.
+ */
+async function hashFile(path: string): Promise<string> {
+  const hash = createHash('sha256');
+  for await (const chunk of createReadStream(path)) {
+    hash.update(chunk);
+  }
+  return hash.digest('hex');
+}
+
+/**
+ * Writes merged content to the output file, or to numbered parts when the
+ * content exceeds `maxBytes`. A header goes before the first segment, followed
+ * by a blank line, unless that segment already starts with it. When streaming,
+ * the output is written as the segments are read and is never split.
+ * @returns The paths that were written, the line count of the merged content,
+ * and the SHA-256 of the bytes written to each path (for a streamed gzip
+ * output, only with `checksum`)
+ */
+async function writeMergedFile(
+  files: string[],
+  outputPath: string,
+  options: AtomicWriteOptions = {},
+  content: MergeContentOptions = {}
+): Promise<{ parts: string[]; lines: number; sha256: string[] }> {
+  if (content.stream) {
+    const tally = new ContentTally();
+    await atomicWrite(outputPath, tally.track(streamSegments(files, content)), options);
+    const contentSha256 = tally.digest();
+    if (content.verify) {
+      await verifyOutput(outputPath, contentSha256, options.gzip);
+    }
+    // Uncompressed, the tallied content is exactly the bytes written; compressed, they are read back
+    if (!options.gzip) {
+      return { parts: [outputPath], lines: tally.lines, sha256: [contentSha256] };
     }
+    return { parts: [outputPath], lines: tally.lines, sha256: content.checksum ? [await hashFile(outputPath)] : [] };
+  }
+
+  const segments: string[] = [];
+  for (const file of files) {
+    segments.push(withEol(await readSegment(file, segments.length === 0), content.eol));
+  }
+  if (content.renumber) {
+    renumberSpeakers(segments);
+  }
+  if (segments.length > 0) {
+    segments[0] = withHeader(segments[0], content.header);
+  }
+
+  const merged = joinSegments(segments);
+  const lines = countLines(merged);
+
+  // Encode each file here so the hash covers the exact bytes written
+  const write = async (path: string, text: string): Promise<string> => {
+    const bytes = encodeContent(text, options);
+    await atomicWrite(path, bytes, options);
+    if (content.verify) {
+      await verifyOutput(path, sha256Hex(text), options.gzip);
+    }
+    return sha256Hex(bytes);
+  };
+
+  const maxBytes = content.maxBytes;
+  if (maxBytes === undefined || Buffer.byteLength(merged, 'utf-8') <= maxBytes) {
+    return { parts: [outputPath], lines, sha256: [await write(outputPath, merged)] };
+  }
 
-    // Never delete the output file
-    if (pathCanonical === outputCanonical) {
+  const parts: string[] = [];
+  const sha256: string[] = [];
+  const groups = planParts(segments, maxBytes);
+  for (let i = 0; i < groups.length; i++) {
+    const path = partPath(outputPath, i + 1);
+    sha256.push(await write(path, joinSegments(groups[i])));
+    parts.push(path);
+  }
+  return { parts, lines, sha256 };
+}
+
+/**
+ * Deletes source files after merging.
+ *
+ * A symlinked source is removed as a link; its target is left untouched.
+ * Any source that resolves to the output (directly or through a symlink) is kept.
+ * @param files The sources to delete
+ * @param outputCanonical Canonical path of the output
+ * @param canonical Canonical path of each source, resolved before writing
+ */
+async function deleteSources(
+  files: string[],
+  outputCanonical: string,
+  canonical: Map<string, string>
+): Promise<void> {
+  for (const path of files) {
+    // Never delete the output file, nor a link that points at it
+    if ((canonical.get(path) ?? (await canonicalize(path))) === outputCanonical) {
       continue;
     }
 
+    // unlink removes the symlink itself rather than following it
     await fs.unlink(path);
   }
 }
 
 /**
- * Executes the merge operation on transcript files.
+ * Settings validated once per run and shared by every output it writes.
  */
-export async function executeMerge(args: MergeArgs): Promise<MergeOutcome> {
+interface MergeSettings {
+  maxOutputBytes?: number;
+  owner?: Owner;
+}
+
+/**
+ * A matched segment and the sort key read from its name.
+ */
+type SegmentDescriptor = { path: string; key: FileSortKey };
+
+/**
+ * Validates the arguments that do not depend on which files match.
+ */
+async function validateMergeArgs(args: MergeArgs): Promise<MergeSettings> {
+  if (args.dateFormat !== undefined) {
+    validateDateFormat(args.dateFormat);
+  }
+  if (args.dayDirFormat !== undefined) {
+    validateDateFormat(args.dayDirFormat);
+  }
+  if (args.outputLocation !== undefined && !['auto', 'nested', 'cwd'].includes(args.outputLocation)) {
+    throw MergeError.invalidOutputLocation(args.outputLocation);
+  }
+  if (args.eol !== undefined && args.eol !== 'lf' && args.eol !== 'crlf') {
+    throw MergeError.invalidEol(args.eol);
+  }
+  if (args.stream && args.maxOutputSize !== undefined) {
+    throw MergeError.conflictingOptions('--stream', '--max-output-size');
+  }
+  if (args.outputRoot !== undefined) {
+    for (const option of ['output', 'outputLocation'] as const) {
+      if (args[option] !== undefined) {
+        throw MergeError.conflictingOptions('--output-root', option === 'output' ? '--output' : '--output-location');
+      }
+    }
+  }
+  const maxOutputBytes = args.maxOutputSize !== undefined ? validateSize(args.maxOutputSize) : undefined;
+  if (args.retries !== undefined && (!Number.isInteger(args.retries) || args.retries < 0)) {
+    throw MergeError.invalidNumber('--retries', args.retries);
+  }
+  let owner: Owner | undefined;
+  if (args.chown !== undefined) {
+    try {
+      owner = await resolveOwner(args.chown);
+    } catch (error: any) {
+      throw MergeError.invalidOwner(args.chown, error.message);
+    }
+  }
+  if (args.minSegments !== undefined && (!Number.isInteger(args.minSegments) || args.minSegments < 1)) {
+    throw MergeError.invalidNumber('--min-segments', args.minSegments);
+  }
+  if (args.maxDepth !== undefined && (!Number.isInteger(args.maxDepth) || args.maxDepth < 0)) {
+    throw MergeError.invalidNumber('--max-depth', args.maxDepth);
+  }
+  return { maxOutputBytes, owner };
+}
+
+/**
+ * Expands the patterns into segments in merge order, without duplicates.
+ * @returns The ordered paths, their descriptors, and each file's metadata
+ */
+async function collectSegments(
+  args: MergeArgs
+): Promise<{ ordered: string[]; descriptors: SegmentDescriptor[]; metadata: Map<string, Stats> }> {
   const collected: string[] = [];
 
-  // Expand glob patterns
-  for (const pattern of args.patterns) {
-    const matches = await glob(pattern, { nodir: true });
+  // Expand glob patterns; a bare directory stands for its transcript segments
+  for (const given of args.patterns) {
+    const pattern = args.baseDir && !isAbsolute(given) ? join(args.baseDir, given) : given;
+    const hidden = args.hidden ?? false;
+    const matches = (await isDirectory(pattern))
+      ? await expandDirectory(pattern, hidden)
+      : withinDepth(await glob(pattern, { nodir: true, dot: hidden }), pattern, args.maxDepth);
 
     if (matches.length === 0) {
       throw MergeError.noMatches(pattern);
     }
+    const kept = await withoutExcluded(matches, args);
+    if (kept.length === 0) {
+      throw MergeError.allExcluded(pattern);
+    }
 
-    collected.push(...matches);
+    // Only .txt and .txt.gz files are segments, so sidecars (.idx, .sha256), JSON Lines
+    // outputs, and notes caught by a broad glob are skipped, as are index files written by update
+    const before = collected.length;
+    for (const match of kept) {
+      if (SEGMENT_EXTENSION_REGEX.test(match) && !(await fileStartsWith(match, INDEX_HEADER))) {
+        collected.push(match);
+      }
+    }
+    if (collected.length === before) {
+      throw MergeError.noSegments(pattern);
+    }
   }
 
-  // Check file sizes before processing
+  // Check file sizes before processing, keeping the metadata for later steps
+  const metadata = new Map<string, Stats>();
   for (const path of collected) {
-    const stats = await fs.stat(path);
-    if (stats.size > MAX_FILE_SIZE) {
-      throw MergeError.fileTooLarge(path, stats.size, MAX_FILE_SIZE);
+    if (metadata.has(path)) {
+      continue;
+    }
+    try {
+      metadata.set(path, await checkSize(path, MAX_FILE_SIZE));
+    } catch (error) {
+      if (error instanceof SizeError) {
+        throw MergeError.fileTooLarge(path, error.size, error.max);
+      }
+      throw error;
     }
   }
 
-  // Extract sort keys and sort files
-  const descriptors = collected.map((path) => ({
-    path,
-    key: extractSortKey(path),
-  }));
+  // Extract sort keys and sort files; with preserveOrder the argument order
+  // stands, and dates still name the output
+  const descriptors = describeSegments(collected, args.dayDirFormat);
+  if (!args.preserveOrder) {
+    sortDescriptors(descriptors);
+  }
+
+  return { ordered: uniquePaths(descriptors), descriptors, metadata };
+}
+
+/**
+ * Drops the matches that an `--exclude` pattern also matches. Excludes are
+ * expanded by the same glob engine as the patterns: one with a directory part
+ * is resolved like a pattern, and a bare name pattern such as `*2025-01-27.txt`
+ * is matched in each directory the matches come from.
+ */
+async function withoutExcluded(matches: string[], args: MergeArgs): Promise<string[]> {
+  if (!args.exclude || args.exclude.length === 0) {
+    return matches;
+  }
 
+  const excluded = new Set<string>();
+  const dirs = [...new Set(matches.map((match) => dirname(match)))];
+  for (const given of args.exclude) {
+    const patterns = /[\\/]/.test(given)
+      ? [args.baseDir && !isAbsolute(given) ? join(args.baseDir, given) : given]
+      : dirs.map((dir) => join(escape(dir), given));
+    for (const pattern of patterns) {
+      for (const match of await glob(pattern, { nodir: true, dot: true })) {
+        excluded.add(resolve(match));
+      }
+    }
+  }
+  return matches.filter((match) => !excluded.has(resolve(match)));
+}
+
+/**
+ * Extracts the sort key of each path. Nested segments outside a day directory
+ * borrow the date the rest of the batch shares.
+ * @throws MergeError if a filename is not a recognized segment name
+ */
+function describeSegments(paths: string[], dayDirFormat?: string): SegmentDescriptor[] {
+  const descriptors = paths.map((path) => ({ path, key: extractSortKey(path, dayDirFormat) }));
+  borrowSharedDate(descriptors.map(({ key }) => key));
+  return descriptors;
+}
+
+/**
+ * Sorts segments chronologically in place: by date, with undated segments
+ * last, then by start time. The sort is stable, so ties keep their order.
+ */
+function sortDescriptors(descriptors: SegmentDescriptor[]): void {
   descriptors.sort((a, b) => {
     // Compare dates first (null dates go last)
     if (a.key.date && b.key.date) {
@@ -389,51 +1249,280 @@ export async function executeMerge(args: MergeArgs): Promise<MergeOutcome> {
     // Then compare start times
     return a.key.start.getTime() - b.key.start.getTime();
   });
+}
 
-  // Remove duplicates while preserving order
+/**
+ * Lists the paths without duplicates, keeping the first occurrence of each
+ * (unsorted duplicates need not be adjacent).
+ */
+function uniquePaths(descriptors: SegmentDescriptor[]): string[] {
   const ordered: string[] = [];
+  const seen = new Set<string>();
   for (const { path } of descriptors) {
-    if (ordered.length === 0 || ordered[ordered.length - 1] !== path) {
+    if (!seen.has(path)) {
+      seen.add(path);
       ordered.push(path);
     }
   }
+  return ordered;
+}
 
-  // Determine output path
-  const outputPath = determineOutputPath(ordered, descriptors, args);
+/**
+ * Counts the segments the patterns match, as merge would collect them, without
+ * reading or writing anything (`merge --count-only`). Fails as merge would on a
+ * pattern with no matches or an unrecognized filename.
+ * @param args Merge arguments; only the pattern options are used
+ * @returns The number of distinct segments
+ */
+export async function countSegments(args: MergeArgs): Promise<number> {
+  return (await collectSegments(args)).ordered.length;
+}
 
-  // Canonicalize output path for comparison
-  let outputCanonical: string;
-  try {
-    outputCanonical = await fs.realpath(outputPath);
-  } catch {
-    outputCanonical = outputPath;
+/**
+ * Sorts transcript segment paths into the order merge would combine them,
+ * without reading or merging anything: chronologically by the date and start
+ * time in their names (undated nested segments last, unless the other segments
+ * share a date), with duplicates removed.
+ * @param paths Flat (YYYYMMDD_HHMMSS_HHMMSS.txt) or nested (YYYY/MM/DD/HHMMSS-HHMMSS.txt) segment paths
+ * @param dayDirFormat date-fns format of single-directory day names, as for `merge --day-dir-format`
+ * @returns The ordered, deduplicated paths
+ * @throws MergeError if a filename is not a recognized segment name
+ */
+export function sortPaths(paths: string[], dayDirFormat?: string): string[] {
+  const descriptors = describeSegments(paths, dayDirFormat);
+  sortDescriptors(descriptors);
+  return uniquePaths(descriptors);
+}
+
+/**
+ * Merges already collected segments into one output (or its parts), then
+ * deletes the sources and runs the post-merge command as requested.
+ * @param nameSuffix Text added after the date in an inferred output name
+ */
+async function mergeSegments(
+  ordered: string[],
+  descriptors: SegmentDescriptor[],
+  metadata: Map<string, Stats>,
+  args: MergeArgs,
+  settings: MergeSettings,
+  nameSuffix = ''
+): Promise<MergeOutcome> {
+  // Determine output path; --compress adds .gz to a name that lacks it
+  const namedPath = determineOutputPath(ordered, descriptors, args, nameSuffix);
+
+  // A pipe or device is streamed into: it cannot be renamed, read back, or dated
+  const special = await isSpecialFile(namedPath);
+  if (special && args.compress) {
+    throw MergeError.specialOutput('--compress', namedPath);
+  }
+  if (special && args.verify) {
+    throw MergeError.specialOutput('--verify', namedPath);
+  }
+  const outputPath = args.compress && !namedPath.endsWith(GZIP_EXTENSION) ? namedPath + GZIP_EXTENSION : namedPath;
+  const gzip = outputPath.endsWith(GZIP_EXTENSION);
+
+  // A directory cannot be renamed over; say so before reading or writing anything
+  if (await isDirectory(outputPath)) {
+    throw MergeError.outputIsDirectory(outputPath);
   }
 
-  // Filter out the output path from sources to prevent self-deletion
+  // Canonicalize output path for comparison
+  const outputCanonical = await canonicalize(outputPath);
+
+  // Filter out the output path from sources to prevent self-deletion. Symlinks
+  // resolving to the output are never followed, so they are excluded as well.
   const sourcesToMerge: string[] = [];
+  const canonical = new Map<string, string>();
   for (const path of ordered) {
-    let pathCanonical: string;
-    try {
-      pathCanonical = await fs.realpath(path);
-    } catch {
-      pathCanonical = path;
+    canonical.set(path, await canonicalize(path));
+    if (canonical.get(path) !== outputCanonical) {
+      sourcesToMerge.push(path);
     }
+  }
 
-    if (pathCanonical !== outputCanonical) {
-      sourcesToMerge.push(path);
+  // Nothing left to consume means nothing would be deleted; fail before writing
+  if (args.expectDelete && !args.noDelete && sourcesToMerge.length === 0) {
+    throw MergeError.nothingToDelete();
+  }
+
+  // A glob that caught fewer segments than expected is usually a typo
+  if (args.minSegments !== undefined && sourcesToMerge.length < args.minSegments) {
+    throw MergeError.tooFewSegments(sourcesToMerge.length, args.minSegments);
+  }
+
+  // Guard a hand-edited output: refuse under --safe, otherwise overwrite with a warning
+  const warnings: Warning[] = [];
+  if (!special && (await isOutputNewer(outputPath, sourcesToMerge, metadata))) {
+    if (args.safe) {
+      throw MergeError.newerOutput(outputPath);
+    }
+    warnings.push(Warning.newerOutput(outputPath));
+  }
+  for (const path of sourcesToMerge) {
+    if (metadata.get(path)?.size === 0) {
+      warnings.push(Warning.emptyInput(path));
+    }
+  }
+  if (args.logFile) {
+    try {
+      await appendWarningLog(args.logFile, outputPath, warnings);
+    } catch (error: any) {
+      throw MergeError.ioError(error.message);
     }
   }
 
   // Write merged file
-  await writeMergedFile(sourcesToMerge, outputPath);
+  const keys = new Map(descriptors.map(({ path, key }) => [path, key]));
+  const { parts, lines, sha256 } = await writeMergedFile(
+    sourcesToMerge,
+    outputPath,
+    {
+      tempDir: args.tempDir,
+      retries: args.retries,
+      gzip,
+      owner: settings.owner,
+      createDirs: !args.noCreateDirs,
+      fsync: !args.noFsync,
+      fsyncDir: args.fsyncDir,
+    },
+    {
+      maxBytes: settings.maxOutputBytes,
+      renumber: args.renumberSpeakers,
+      header: args.dateHeader ? dateHeader(sourcesToMerge, keys) : undefined,
+      stream: args.stream,
+      eol: args.eol === undefined ? undefined : args.eol === 'crlf' ? '\r\n' : '\n',
+      verify: args.verify,
+      checksum: args.checksum,
+    }
+  );
+
+  const checksum: MergeChecksum | undefined = args.checksum
+    ? {
+        sha256,
+        sourceBytes: sourcesToMerge.reduce((total, path) => total + (metadata.get(path)?.size ?? 0), 0),
+      }
+    : undefined;
 
   // Delete sources if requested
   if (!args.noDelete) {
-    await deleteSources(sourcesToMerge, outputPath);
+    await deleteSources(sourcesToMerge, outputCanonical, canonical);
   }
 
+  // Only run after everything above succeeded
+  const postMergeStatus = args.postMergeCmd ? await runPostMergeCommand(args.postMergeCmd, parts) : undefined;
+
   return {
     files: sourcesToMerge,
     outputPath,
+    parts,
+    segments: sourcesToMerge.length,
+    lines,
+    warnings,
+    checksum,
+    deleted: !args.noDelete,
+    span: computeSpan(sourcesToMerge, keys),
+    postMergeStatus,
   };
 }
+
+/**
+ * Executes the merge operation on transcript files.
+ */
+export async function executeMerge(args: MergeArgs): Promise<MergeOutcome> {
+  const settings = await validateMergeArgs(args);
+  const named = args.start !== undefined || args.end !== undefined;
+  if (named && args.output !== undefined) {
+    throw MergeError.conflictingOptions(args.start !== undefined ? '--start' : '--end', '--output');
+  }
+  const start = args.start !== undefined ? validateTime(args.start) : undefined;
+  const end = args.end !== undefined ? validateTime(args.end) : undefined;
+
+  const { ordered, descriptors, metadata } = await collectSegments(args);
+
+  // Explicit times replace the ones read from the first and last filenames
+  let nameSuffix = '';
+  if (named && ordered.length > 0) {
+    const keys = new Map(descriptors.map(({ path, key }) => [path, key]));
+    const last = keys.get(ordered[ordered.length - 1])!;
+    nameSuffix = spanSuffix(start ?? keys.get(ordered[0])!.start, end ?? last.end ?? last.start);
+  }
+  return mergeSegments(ordered, descriptors, metadata, args, settings, nameSuffix);
+}
+
+/**
+ * Formats the `_HHMMSS-HHMMSS` part of a name that carries times.
+ */
+function spanSuffix(start: Date, end: Date): string {
+  return `_${format(start, TIME_FORMAT_COMPACT)}-${format(end, TIME_FORMAT_COMPACT)}`;
+}
+
+/**
+ * Splits ordered segments wherever the time from one segment's end (or start,
+ * without a valid end) to the next one's start exceeds the gap.
+ */
+function groupByGap(ordered: string[], keys: Map<string, FileSortKey>, gapMs: number): string[][] {
+  const groups: string[][] = [];
+  let previousEnd: Date | null = null;
+  for (const path of ordered) {
+    const key = keys.get(path)!;
+    const start = atDate(key.start, key.date);
+    if (previousEnd === null || start.getTime() - previousEnd.getTime() > gapMs) {
+      groups.push([]);
+    }
+    groups[groups.length - 1].push(path);
+    previousEnd = atDate(key.end ?? key.start, key.date);
+  }
+  return groups;
+}
+
+/**
+ * Splits ordered segments by date, keeping the order within each day.
+ * Segments without a date form a group of their own.
+ */
+function groupByDay(ordered: string[], keys: Map<string, FileSortKey>): string[][] {
+  const days = new Map<number | null, string[]>();
+  for (const path of ordered) {
+    const day = keys.get(path)!.date?.getTime() ?? null;
+    days.set(day, [...(days.get(day) ?? []), path]);
+  }
+  return [...days.values()];
+}
+
+/**
+ * Merges groups of segments into outputs of their own. With `outputRoot`, each
+ * day's segments become one output under the root; with `groupByGap`, each run
+ * of segments separated by no more than the gap does (within a day, when both
+ * are set), named `<date>_HHMMSS-HHMMSS.txt` after the run's first start and
+ * last end. Groups are written in order; a failure stops the rest.
+ * @param args Merge arguments with `groupByGap` or `outputRoot` set
+ * @returns One outcome per group
+ */
+export async function executeMergeGroups(args: MergeArgs): Promise<MergeOutcome[]> {
+  if (args.groupByGap === undefined && args.outputRoot === undefined) {
+    throw MergeError.missingOption('--group-by-gap or --output-root');
+  }
+  const grouping = args.groupByGap !== undefined ? '--group-by-gap' : '--output-root';
+  for (const option of ['output', 'start', 'end'] as const) {
+    if (args[option] !== undefined) {
+      throw MergeError.conflictingOptions(grouping, `--${option}`);
+    }
+  }
+  const gapMs = args.groupByGap !== undefined ? validateDuration('--group-by-gap', args.groupByGap) : undefined;
+  const settings = await validateMergeArgs(args);
+  const { ordered, descriptors, metadata } = await collectSegments(args);
+
+  const keys = new Map(descriptors.map(({ path, key }) => [path, key]));
+  const days = args.outputRoot !== undefined ? groupByDay(ordered, keys) : [ordered];
+  const groups = gapMs !== undefined ? days.flatMap((day) => groupByGap(day, keys, gapMs)) : days;
+
+  const outcomes: MergeOutcome[] = [];
+  for (const group of groups) {
+    const first = keys.get(group[0])!;
+    const last = keys.get(group[group.length - 1])!;
+    const suffix = gapMs !== undefined ? spanSuffix(first.start, last.end ?? last.start) : '';
+    const members = new Set(group);
+    const groupDescriptors = descriptors.filter(({ path }) => members.has(path));
+    outcomes.push(await mergeSegments(group, groupDescriptors, metadata, args, settings, suffix));
+  }
+  return outcomes;
+}
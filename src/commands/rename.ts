@@ -0,0 +1,103 @@
+/**
+ * Rename command implementation.
+ */
+
+import { promises as fs } from 'fs';
+import { dirname, resolve } from 'path';
+import { glob } from 'glob';
+import { RenameArgs } from '../types.js';
+import { TranscriptProcessor } from '../transcript.js';
+import { moveIntoPlace, readFileWithSizeLimit } from '../utils/fileio.js';
+import { validateDate, validateTime, fileExists } from '../utils/validation.js';
+import { resolveOutputPath } from './update.js';
+import { RenameError } from '../errors.js';
+import { MAX_FILE_SIZE } from '../constants.js';
+
+/**
+ * A file and the canonical path it was (or would be) moved to.
+ */
+export interface RenameMove {
+  /** The file as matched. */
+  source: string;
+  /** Its canonical path in the output layout. */
+  target: string;
+}
+
+/**
+ * Result of the rename operation.
+ */
+export interface RenameOutcome {
+  /** Files that were moved, or would be with `dryRun`. Files already in place are omitted. */
+  moves: RenameMove[];
+  /** True if nothing was moved because `dryRun` was set. */
+  dryRun: boolean;
+}
+
+/**
+ * Reads a transcript's first and last clock times on the given date.
+ * The file's timestamps are taken as they are, as with `update --offset 00:00:00`.
+ */
+async function readSpan(path: string, date: Date): Promise<{ first: Date; last: Date }> {
+  let contents: string;
+  try {
+    contents = await readFileWithSizeLimit(path, MAX_FILE_SIZE);
+  } catch (error: any) {
+    throw RenameError.ioError(`${path}: ${error.message}`);
+  }
+
+  const transcript = TranscriptProcessor.adjust(contents, validateTime('00:00:00'), date);
+  return { first: transcript.firstTimestamp, last: transcript.lastTimestamp };
+}
+
+/**
+ * Executes the rename operation: moves each matched transcript to the name
+ * `update` would give it, derived from the first and last timestamps in its
+ * content. Every target is checked before anything is moved, so a clash leaves
+ * all files where they were.
+ * @param args Rename arguments
+ * @returns The planned or performed moves
+ */
+export async function executeRename(args: RenameArgs): Promise<RenameOutcome> {
+  if (args.date === undefined) {
+    throw RenameError.missingDate();
+  }
+  const date = validateDate(args.date, args.dateInputFormat);
+
+  const sources: string[] = [];
+  for (const pattern of args.patterns) {
+    const matches = (await glob(pattern, { nodir: true })).sort();
+    if (matches.length === 0) {
+      throw RenameError.noMatches(pattern);
+    }
+    sources.push(...matches.filter((match) => !sources.includes(match)));
+  }
+
+  // Plan every move first
+  const moves: RenameMove[] = [];
+  const targets = new Set<string>();
+  for (const source of sources) {
+    const { first, last } = await readSpan(source, date);
+    const target = resolveOutputPath(args, first, last);
+    if (resolve(target) === resolve(source)) {
+      continue;
+    }
+    if (targets.has(resolve(target)) || (await fileExists(target))) {
+      throw RenameError.targetExists(source, target);
+    }
+    targets.add(resolve(target));
+    moves.push({ source, target });
+  }
+
+  if (!args.dryRun) {
+    for (const { source, target } of moves) {
+      try {
+        await fs.mkdir(dirname(target), { recursive: true });
+        await moveIntoPlace(source, target);
+      } catch (error: any) {
+        throw RenameError.ioError(error.message);
+      }
+    }
+  }
+
+  return { moves, dryRun: args.dryRun ?? false };
+}
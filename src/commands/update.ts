@@ -3,14 +3,63 @@
  */
 
 import { promises as fs } from 'fs';
-import { join } from 'path';
-import { format } from 'date-fns';
-import { UpdateArgs } from '../types.js';
-import { TranscriptProcessor } from '../transcript.js';
-import { atomicWrite, readFileWithSizeLimit } from '../utils/fileio.js';
-import { validateTime, validateDate, fileExists } from '../utils/validation.js';
-import { UpdateError } from '../errors.js';
-import { MAX_FILE_SIZE, DATE_FORMAT_COMPACT, YEAR_FORMAT, MONTH_FORMAT, DAY_FORMAT } from '../constants.js';
+import { join, basename, dirname, extname } from 'path';
+import { createHash } from 'crypto';
+import { format, parse, isValid, addMilliseconds } from 'date-fns';
+import { UpdateArgs, FlatName, NestedLayout } from '../types.js';
+import {
+  TranscriptProcessor,
+  TimestampListing,
+  ListedTimestamp,
+  AdjustOptions,
+  TranscriptUpdate,
+  AdjustedTimestamp,
+} from '../transcript.js';
+import {
+  atomicWrite,
+  encodeContent,
+  readFileWithSizeLimit,
+  probeWritable,
+  fileStartsWith,
+  isDirectory,
+  isSpecialFile,
+  AtomicWriteOptions,
+} from '../utils/fileio.js';
+import { unifiedDiff } from '../utils/diff.js';
+import { resolveOwner, Owner } from '../utils/ownership.js';
+import { joinSegments, countLines } from './merge.js';
+import {
+  validateTime,
+  validateTimeArgument,
+  validateDate,
+  validateFps,
+  validateCount,
+  validateOffset,
+  validateTolerance,
+  validateRound,
+  validateBound,
+  fileExists,
+} from '../utils/validation.js';
+import { UpdateError, TranscriptError, SizeError } from '../errors.js';
+import { Warning, appendWarningLog, warningExitCode } from '../warnings.js';
+import {
+  MAX_FILE_SIZE,
+  DATE_FORMAT_COMPACT,
+  DATE_FORMAT_DASHED,
+  TIME_FORMAT,
+  TIME_FORMAT_COMPACT,
+  DATETIME_FORMAT,
+  INDEX_HEADER,
+  TIMESTAMP_INDEX_EXTENSION,
+  CHECKSUM_EXTENSION,
+  JSONL_EXTENSION,
+  ISO_DATETIME_FORMAT,
+  YEAR_FORMAT,
+  MONTH_FORMAT,
+  DAY_FORMAT,
+  ISO_WEEK_YEAR_FORMAT,
+  ISO_WEEK_FORMAT,
+} from '../constants.js';
 
 /**
  * Result of the update operation.
@@ -18,93 +67,1001 @@ import { MAX_FILE_SIZE, DATE_FORMAT_COMPACT, YEAR_FORMAT, MONTH_FORMAT, DAY_FORM
 export interface UpdateOutcome {
   /** Path where the output was written. */
   outputPath: string;
+  /** First adjusted timestamp in the output. */
+  firstTimestamp: Date;
+  /** Last adjusted timestamp in the output. */
+  lastTimestamp: Date;
   /** Warning: timestamps in the input were not in chronological order. */
   hasOutOfOrderTimestamps: boolean;
+  /** Non-fatal conditions encountered while updating. */
+  warnings: Warning[];
+  /** Unified diff of the input against the adjusted output when previewing with `diff`. */
+  diff?: string;
+  /** The first lines of the written output, when requested with `preview`. */
+  preview?: string[];
+  /** Path of the timestamps-only index, when written with `timestampIndex`. */
+  timestampIndexPath?: string;
+  /** Path of the SHA-256 sidecar, when written with `checksum`. */
+  checksumPath?: string;
+}
+
+/**
+ * Result of updating every transcript in a directory.
+ */
+export interface UpdateBatchOutcome {
+  /** Outcomes of the files that were updated, in filename order. */
+  outcomes: UpdateOutcome[];
+  /** Files that could not be updated, with the reason. */
+  failures: UpdateFailure[];
+}
+
+/**
+ * A file that could not be updated in a directory batch.
+ */
+export interface UpdateFailure {
+  /** The input file. */
+  file: string;
+  /** Why it was not updated. */
+  message: string;
+}
+
+/**
+ * Infers a transcript's start from a flat-format name (YYYYMMDD_HHMMSS_HHMMSS.txt).
+ * @param path The transcript path
+ * @returns The start date and time, or null if the name does not carry one
+ */
+export function inferStartFromFilename(path: string): { date: Date; time: Date } | null {
+  const match = /^(\d{8})_(\d{6})_\d{6}$/.exec(basename(path, '.txt'));
+  if (!match) {
+    return null;
+  }
+
+  const start = parse(match[1] + match[2], DATE_FORMAT_COMPACT + TIME_FORMAT_COMPACT, new Date());
+  if (!isValid(start)) {
+    return null;
+  }
+
+  const date = new Date(start);
+  date.setHours(0, 0, 0, 0);
+  return { date, time: start };
+}
+
+/**
+ * Builds the nested output name: YYYY/MM/DD/HHMMSS-HHMMSS.txt, or
+ * YYYY/Www/HHMMSS-HHMMSS.txt under the `iso-week` layout, where YYYY is the ISO
+ * week-numbering year (so 2024-12-30 is in 2025/W01).
+ * The date directories come from the last timestamp, so a transcript that runs
+ * past midnight lands under the day it ended.
+ * @param firstTimestamp First adjusted timestamp
+ * @param lastTimestamp Last adjusted timestamp
+ * @param layout Directory layout (default `day`)
+ * @returns The relative output path
+ */
+export function nestedOutputName(firstTimestamp: Date, lastTimestamp: Date, layout: NestedLayout = 'day'): string {
+  const startTime = format(firstTimestamp, TIME_FORMAT_COMPACT);
+  const endTime = format(lastTimestamp, TIME_FORMAT_COMPACT);
+  const filename = `${startTime}-${endTime}.txt`;
+  if (layout === 'iso-week') {
+    return join(format(lastTimestamp, ISO_WEEK_YEAR_FORMAT), format(lastTimestamp, ISO_WEEK_FORMAT), filename);
+  }
+
+  const year = format(lastTimestamp, YEAR_FORMAT);
+  const month = format(lastTimestamp, MONTH_FORMAT);
+  const day = format(lastTimestamp, DAY_FORMAT);
+  return join(year, month, day, filename);
+}
+
+/**
+ * Builds the flat output filename: YYYYMMDD_HHMMSS_HHMMSS.txt, or
+ * YYYYMMDD_HHMMSS.txt under the `start` naming policy.
+ * @param date Date for the filename prefix
+ * @param firstTimestamp First adjusted timestamp
+ * @param lastTimestamp Last adjusted timestamp
+ * @param name Which times the name carries (default `start-end`)
+ * @returns The output filename
+ */
+export function flatOutputName(
+  date: Date,
+  firstTimestamp: Date,
+  lastTimestamp: Date,
+  name: FlatName = 'start-end'
+): string {
+  const dateStr = format(date, DATE_FORMAT_COMPACT);
+  const startTime = format(firstTimestamp, TIME_FORMAT_COMPACT);
+  if (name === 'start') {
+    return `${dateStr}_${startTime}.txt`;
+  }
+  const endTime = format(lastTimestamp, TIME_FORMAT_COMPACT);
+  return `${dateStr}_${startTime}_${endTime}.txt`;
 }
 
 /**
  * Resolves the output path based on the request and timestamps.
- * @param args Update arguments
+ * @param args Layout settings (`flat`, `flatName`, `layout`, `name`, and `outputDir`)
  * @param firstTimestamp First timestamp in transcript
  * @param lastTimestamp Last timestamp in transcript
  * @returns The output file path
  */
-function resolveOutputPath(
-  args: UpdateArgs,
+export function resolveOutputPath(
+  args: Pick<UpdateArgs, 'flat' | 'flatName' | 'layout' | 'name' | 'outputDir'>,
   firstTimestamp: Date,
   lastTimestamp: Date
 ): string {
-  // Use the date from the last timestamp (handles midnight overflow)
-  const effectiveDate = lastTimestamp;
-
   if (args.flat) {
-    // Flat format: YYYYMMDD_HHMMSS_HHMMSS.txt in current directory
-    const dateStr = format(effectiveDate, DATE_FORMAT_COMPACT);
-    const startTime = format(firstTimestamp, 'HHmmss');
-    const endTime = format(lastTimestamp, 'HHmmss');
-    const filename = `${dateStr}_${startTime}_${endTime}.txt`;
-    return join(process.cwd(), filename);
+    // Flat format in current directory, dated by the last timestamp (handles midnight overflow)
+    const name = args.name ?? flatOutputName(lastTimestamp, firstTimestamp, lastTimestamp, args.flatName);
+    return join(process.cwd(), name);
   } else {
-    // Nested format: output-dir/YYYY/MM/DD/HHMMSS-HHMMSS.txt
-    const year = format(effectiveDate, YEAR_FORMAT);
-    const month = format(effectiveDate, MONTH_FORMAT);
-    const day = format(effectiveDate, DAY_FORMAT);
-    const startTime = format(firstTimestamp, 'HHmmss');
-    const endTime = format(lastTimestamp, 'HHmmss');
-    const filename = `${startTime}-${endTime}.txt`;
-
+    // Nested format under the output directory; a custom name keeps the date directories
     const outputDir = args.outputDir || '';
-    return join(outputDir, year, month, day, filename);
+    const nested = nestedOutputName(firstTimestamp, lastTimestamp, args.layout);
+    return join(outputDir, args.name !== undefined ? join(dirname(nested), args.name) : nested);
   }
 }
 
 /**
- * Executes the update operation on a transcript file.
- * @param args Update arguments
- * @returns Update outcome
+ * Writes an index listing each output path with its span, in processing order.
+ * The file starts with {@link INDEX_HEADER} so merge never picks it up as a segment.
+ * @param indexPath Where to write the index
+ * @param outcomes Update outcomes to list
  */
-export async function executeUpdate(args: UpdateArgs): Promise<UpdateOutcome> {
-  // Validate time and date
-  const startTime = validateTime(args.time);
-  const effectiveDate = validateDate(args.date);
+export async function writeIndex(
+  indexPath: string,
+  outcomes: UpdateOutcome[],
+  options: AtomicWriteOptions = {}
+): Promise<void> {
+  const lines = [INDEX_HEADER];
+  for (const outcome of outcomes) {
+    const first = format(outcome.firstTimestamp, DATETIME_FORMAT);
+    const last = format(outcome.lastTimestamp, DATETIME_FORMAT);
+    lines.push(`${outcome.outputPath}\t${first}\t${last}`);
+  }
+
+  try {
+    await atomicWrite(indexPath, lines.join('\n') + '\n', options);
+  } catch (error: any) {
+    throw UpdateError.ioError(error.message);
+  }
+}
+
+/**
+ * Renders an adjusted transcript as JSON Lines (`--format jsonl`), one object
+ * per line of the body: `{"absolute":"2024-12-25T18:01:13","text":"Speaker 1: Hello","had_timestamp":true}`.
+ * Plain lines have a null `absolute` and the whole line as `text`. With `fps`,
+ * `absolute` keeps milliseconds. The timestamped lines come from the adjust
+ * result rather than re-parsing the body, so they stay right after `--trim`,
+ * `--skip-lines`, or `--collapse-speakers` have changed the lines.
+ * @param transcript The adjusted transcript
+ * @param options The options it was adjusted with; only `fps` is consulted
+ * @returns The entries, each ending with a newline
+ */
+export function formatJsonLines(transcript: TranscriptUpdate, options: AdjustOptions = {}): string {
+  const timestamps = new Map(transcript.timestamps.map((entry) => [entry.lineNumber, entry]));
+  const timeFormat = options.fps !== undefined ? `${ISO_DATETIME_FORMAT}.SSS` : ISO_DATETIME_FORMAT;
+
+  const { body: text } = transcript;
+  const body = text.endsWith('\n') ? text.substring(0, text.length - 1) : text;
+  if (body.length === 0) {
+    return '';
+  }
+  return body
+    .split('\n')
+    .map((line, index) => {
+      const timestamped = timestamps.get(index + 1);
+      const entry = timestamped
+        ? {
+            absolute: format(timestamped.timestamp, timeFormat),
+            text: line.substring(timestamped.textStart).trim(),
+            had_timestamp: true,
+          }
+        : { absolute: null, text: line.replace(/\r$/, ''), had_timestamp: false };
+      return JSON.stringify(entry) + '\n';
+    })
+    .join('');
+}
+
+/**
+ * Derives the timestamps-only index path for an output: 180122-180132.txt
+ * becomes 180122-180132.idx.
+ */
+export function timestampIndexPath(outputPath: string): string {
+  const extension = extname(outputPath);
+  return outputPath.substring(0, outputPath.length - extension.length) + TIMESTAMP_INDEX_EXTENSION;
+}
+
+/**
+ * Writes one `HH:MM:SS<tab>line` entry per adjusted timestamp, pointing at
+ * its 1-based line in the output, for search tools.
+ * @param indexPath Where to write the index
+ * @param timestamps The adjusted timestamps in output order
+ */
+async function writeTimestampIndex(
+  indexPath: string,
+  timestamps: AdjustedTimestamp[],
+  options: AtomicWriteOptions
+): Promise<void> {
+  const lines = timestamps.map(({ lineNumber, timestamp }) => `${format(timestamp, TIME_FORMAT)}\t${lineNumber}`);
+
+  try {
+    await atomicWrite(indexPath, lines.map((line) => line + '\n').join(''), options);
+  } catch (error: any) {
+    throw UpdateError.ioError(error.message);
+  }
+}
+
+/**
+ * Writes a `<output>.sha256` sidecar in the `<hash>  <filename>` form that
+ * `sha256sum -c` checks, run from the output's directory.
+ * @param outputPath The output the sidecar vouches for
+ * @param bytes The exact bytes written to the output
+ * @returns The sidecar path
+ */
+async function writeChecksumSidecar(outputPath: string, bytes: Buffer, options: AtomicWriteOptions): Promise<string> {
+  const sidecarPath = outputPath + CHECKSUM_EXTENSION;
+  const hash = createHash('sha256').update(bytes).digest('hex');
+
+  try {
+    await atomicWrite(sidecarPath, `${hash}  ${basename(outputPath)}\n`, options);
+  } catch (error: any) {
+    throw UpdateError.ioError(error.message);
+  }
+  return sidecarPath;
+}
+
+/**
+ * Fails if the directory already holds transcripts, for `exclusiveDir`.
+ * A directory that does not exist yet is empty.
+ */
+async function checkExclusiveDir(dir: string): Promise<void> {
+  let entries: string[];
+  try {
+    entries = await fs.readdir(dir);
+  } catch (error: any) {
+    if (error.code === 'ENOENT') {
+      return;
+    }
+    throw UpdateError.ioError(error.message);
+  }
+
+  const existing = entries.filter((entry) => entry.endsWith('.txt')).sort();
+  if (existing.length > 0) {
+    throw UpdateError.directoryNotEmpty(dir, existing);
+  }
+}
+
+/**
+ * Collects the atomic write settings shared by every file update writes.
+ */
+async function writeOptions(args: UpdateArgs): Promise<AtomicWriteOptions> {
+  const retries = args.retries !== undefined ? validateCount('--retries', args.retries) : undefined;
+  let owner: Owner | undefined;
+  if (args.chown !== undefined) {
+    try {
+      owner = await resolveOwner(args.chown);
+    } catch (error: any) {
+      throw UpdateError.invalidOwner(args.chown, error.message);
+    }
+  }
+  return {
+    tempDir: args.tempDir,
+    retries,
+    owner,
+    createDirs: !args.noCreateDirs,
+    fsync: !args.noFsync,
+    fsyncDir: args.fsyncDir,
+  };
+}
+
+/**
+ * Resolves the rebase start time, date, and extra offset from the arguments.
+ * `--time` rebases relative timestamps onto a start time (optionally carrying
+ * the date as `YYYY-MM-DD HH:MM:SS`); `--offset` keeps the timestamps as clock
+ * times (a midnight base) and shifts them by a duration.
+ *
+ * `--auto-base` takes the base from the input's first timestamp, so every
+ * timestamp keeps its own time, shifted only by any `--offset`. The date comes
+ * from that line's `YYYY-MM-DD` prefix, or from `--date` when it has none.
+ * @param autoBase The input's first timestamp, read when `autoBase` is set
+ * @param dateFlag The flag that supplied `args.date`, named in conflict errors
+ */
+function resolveBase(
+  args: UpdateArgs,
+  autoBase?: { time: Date; date: string | null },
+  dateFlag = '--date'
+): { startTime: Date; effectiveDate: Date; offset: number } {
+  let startTime: Date;
+  let embeddedDate: Date | undefined;
+  let offset = 0;
 
-  // Check if file exists
+  if (autoBase) {
+    if (args.time !== undefined) {
+      throw UpdateError.conflictingOptions('--auto-base', '--time');
+    }
+    // Rebasing the first timestamp onto itself leaves every time as it was
+    startTime = validateTime('00:00:00');
+    offset = args.offset !== undefined ? validateOffset(args.offset) : 0;
+    if (autoBase.date !== null) {
+      if (args.date !== undefined) {
+        throw UpdateError.conflictingOptions('--auto-base with a dated first line', dateFlag);
+      }
+      embeddedDate = validateDate(autoBase.date);
+    }
+  } else if (args.offset !== undefined) {
+    if (args.time !== undefined) {
+      throw UpdateError.conflictingOptions('--offset', '--time');
+    }
+    startTime = validateTime('00:00:00');
+    offset = validateOffset(args.offset);
+  } else {
+    if (args.time === undefined) {
+      throw UpdateError.missingOption('--time <time>');
+    }
+    ({ time: startTime, date: embeddedDate } = validateTimeArgument(args.time));
+  }
+
+  if (embeddedDate) {
+    if (args.date !== undefined) {
+      throw UpdateError.conflictingOptions('--time with a date', dateFlag);
+    }
+    return { startTime, effectiveDate: embeddedDate, offset };
+  }
+
+  if (args.date === undefined) {
+    throw UpdateError.missingOption('--date <date>');
+  }
+  return { startTime, effectiveDate: validateDate(args.date, args.dateInputFormat), offset };
+}
+
+/**
+ * Reads the input's first timestamp for `--auto-base`. Only the line-skipping
+ * options are consulted; the rest are validated later.
+ */
+async function readAutoBase(args: UpdateArgs): Promise<{ time: Date; date: string | null }> {
   if (!(await fileExists(args.file))) {
     throw UpdateError.fileNotFound(args.file);
   }
+  const options = { skipLines: args.skipLines, commentPrefix: args.commentPrefix };
+  const first = TranscriptProcessor.firstTimestamp(await readInput(args.file), options);
+  if (!first) {
+    throw TranscriptError.noTimestamps();
+  }
+  return first;
+}
+
+/**
+ * Infers the date for `--auto-date` from the input's flat-format name
+ * (YYYYMMDD_HHMMSS_HHMMSS.txt); it replaces any `--date`.
+ * @returns The inferred date as YYYY-MM-DD
+ * @throws UpdateError if the name does not carry a date
+ */
+function inferAutoDate(args: UpdateArgs): string {
+  if (args.continueFrom !== undefined) {
+    throw UpdateError.conflictingOptions('--auto-date', '--continue-from');
+  }
+  const start = inferStartFromFilename(args.file);
+  if (!start) {
+    throw UpdateError.uninferableDate(args.file);
+  }
+  return format(start.date, DATE_FORMAT_DASHED);
+}
 
-  // Read file with size limit
-  let contents: string;
+/**
+ * Whether the date inferred by `--auto-date` differs from the `--date` it
+ * replaces; an unparsable `--date` counts as different.
+ */
+function overridesDate(autoDate: string, args: UpdateArgs): boolean {
   try {
-    contents = await readFileWithSizeLimit(args.file, MAX_FILE_SIZE);
+    return format(validateDate(args.date!, args.dateInputFormat), DATE_FORMAT_DASHED) !== autoDate;
+  } catch {
+    return true;
+  }
+}
+
+/**
+ * Resolves the base for `--continue-from`: the input continues where the
+ * previous transcript ends. The previous transcript's times are read as clock
+ * times on the date of its first line's `YYYY-MM-DD` prefix (or `--date`), so
+ * one that runs past midnight carries the next day over.
+ * @throws UpdateError if the previous transcript is missing or has no timestamps
+ */
+async function resolveContinueBase(
+  args: UpdateArgs,
+  previous: string
+): Promise<{ startTime: Date; effectiveDate: Date; offset: number }> {
+  if (args.time !== undefined) {
+    throw UpdateError.conflictingOptions('--continue-from', '--time');
+  }
+  if (args.offset !== undefined) {
+    throw UpdateError.conflictingOptions('--continue-from', '--offset');
+  }
+  if (args.autoBase) {
+    throw UpdateError.conflictingOptions('--continue-from', '--auto-base');
+  }
+  if (!(await fileExists(previous))) {
+    throw UpdateError.fileNotFound(previous);
+  }
+
+  const options: AdjustOptions = {
+    fps: args.fps !== undefined ? validateFps(args.fps) : undefined,
+    dualTimestamps: args.dualTimestamps,
+    skipLines: args.skipLines,
+    commentPrefix: args.commentPrefix,
+  };
+  const contents = await readInput(previous);
+  const first = TranscriptProcessor.firstTimestamp(contents, options);
+  if (!first) {
+    throw UpdateError.nothingToContinue(previous);
+  }
+
+  let date: Date;
+  if (first.date !== null) {
+    if (args.date !== undefined) {
+      throw UpdateError.conflictingOptions('--continue-from with a dated first line', '--date');
+    }
+    date = validateDate(first.date);
+  } else {
+    if (args.date === undefined) {
+      throw UpdateError.missingOption('--date <date>');
+    }
+    date = validateDate(args.date, args.dateInputFormat);
+  }
+
+  const midnight = validateTime('00:00:00');
+  const { lastTimestamp } = TranscriptProcessor.adjust(contents, midnight, date, options);
+  return { startTime: lastTimestamp, effectiveDate: lastTimestamp, offset: 0 };
+}
+
+/**
+ * Reports each adjusted timestamp on stderr for `--trace`, one
+ * `file:line: original -> YYYY-MM-DD HH:MM:SS` line each, leaving stdout and
+ * the output untouched.
+ */
+function traceTimestamps(file: string): AdjustOptions['trace'] {
+  return (lineNumber, original, adjusted) => {
+    process.stderr.write(`${file}:${lineNumber}: ${original} -> ${format(adjusted, DATETIME_FORMAT)}\n`);
+  };
+}
+
+/**
+ * Reads a transcript, enforcing the input size limit.
+ */
+async function readInput(file: string): Promise<string> {
+  try {
+    return await readFileWithSizeLimit(file, MAX_FILE_SIZE);
   } catch (error: any) {
-    if (error.message.includes('File too large')) {
-      const stats = await fs.stat(args.file);
-      throw UpdateError.fileTooLarge(stats.size, MAX_FILE_SIZE);
+    if (error instanceof SizeError) {
+      throw UpdateError.fileTooLarge(error.size, error.max);
     }
     throw UpdateError.ioError(error.message);
   }
+}
 
-  // Process the transcript
-  let transcript;
+/**
+ * Takes the first lines of an adjusted body. Lines are split on whole
+ * characters, so multibyte text is never cut.
+ */
+function previewLines(body: string, count: number): string[] {
+  const lines = body.split('\n', count + 1);
+  if (lines.length > count || lines[lines.length - 1] !== '') {
+    return lines.slice(0, count);
+  }
+  return lines.slice(0, -1);
+}
+
+/**
+ * Drops whitespace-only lines from the start and end of an adjusted body,
+ * keeping a final newline and interior blank lines. Timestamp line numbers
+ * move up by the lines removed from the top.
+ */
+function trimBlankLines(transcript: TranscriptUpdate): TranscriptUpdate {
+  const lines = transcript.body.split('\n');
+  const trailingNewline = transcript.body.endsWith('\n');
+  let start = 0;
+  let end = lines.length;
+  while (start < end && lines[start].trim() === '') {
+    start++;
+  }
+  while (end > start && lines[end - 1].trim() === '') {
+    end--;
+  }
+
+  const kept = lines.slice(start, end).join('\n');
+  return {
+    ...transcript,
+    body: kept.length > 0 && trailingNewline ? kept + '\n' : kept,
+    timestamps: transcript.timestamps.map((entry) => ({ ...entry, lineNumber: entry.lineNumber - start })),
+  };
+}
+
+/**
+ * Checks that an output directory exists (creating it unless `noCreateDirs`
+ * is set) and that files can be created in it.
+ * @throws UpdateError if the directory is missing or cannot be written
+ */
+async function assertWritable(dir: string, args: UpdateArgs): Promise<void> {
+  if (args.noCreateDirs && !(await isDirectory(dir))) {
+    throw UpdateError.outputDirMissing(dir);
+  }
+  try {
+    await probeWritable(dir, !args.noCreateDirs);
+  } catch (error: any) {
+    throw UpdateError.outputNotWritable(dir, error.message);
+  }
+}
+
+/**
+ * Rejects a zero-byte input unless `allowEmpty` is set.
+ * @param file The input, for the error message
+ * @param text The input's contents
+ * @param args Update arguments, for `allowEmpty`
+ * @throws UpdateError if the input is empty and empty inputs are not allowed
+ */
+function assertNotEmpty(file: string, text: string, args: UpdateArgs): void {
+  if (text.length === 0 && !args.allowEmpty) {
+    throw UpdateError.emptyInput(file);
+  }
+}
+
+/**
+ * The result of adjusting an empty input: no content, spanning just the start time.
+ */
+function emptyTranscript(startTime: Date, effectiveDate: Date, offset: number): TranscriptUpdate {
+  const start = new Date(effectiveDate);
+  start.setHours(startTime.getHours(), startTime.getMinutes(), startTime.getSeconds(), 0);
+  const adjusted = addMilliseconds(start, offset);
+  return {
+    body: '',
+    firstTimestamp: adjusted,
+    lastTimestamp: adjusted,
+    hasOutOfOrderTimestamps: false,
+    hasTiedTimestamps: false,
+    malformedLines: 0,
+    timestamps: [],
+  };
+}
+
+/**
+ * Lists the timestamps detected in a transcript without adjusting or writing
+ * anything (`update --list-only`). Start time and date are not required.
+ * @param args Update arguments; only the file and parsing options are used
+ * @returns The detected timestamps and the count of plain lines
+ */
+export async function listTimestamps(args: UpdateArgs): Promise<TimestampListing> {
+  const { contents, options } = await readForParsing(args);
+  return TranscriptProcessor.list(contents, options);
+}
+
+/**
+ * Counts the timestamped lines in a transcript without adjusting or writing
+ * anything (`update --count-only`).
+ * @param args Update arguments; only the file and parsing options are used
+ * @returns The number of lines with a detected timestamp
+ */
+export async function countTimestamps(args: UpdateArgs): Promise<number> {
+  return (await listTimestamps(args)).timestamps.length;
+}
+
+/**
+ * Lists the distinct speaker labels in a transcript in order of first
+ * appearance, without adjusting or writing anything (`update --speakers-only`).
+ * @param args Update arguments; only the file and parsing options are used
+ * @returns The speaker labels, each once
+ */
+export async function listSpeakers(args: UpdateArgs): Promise<string[]> {
+  const { contents, options } = await readForParsing(args);
+  return TranscriptProcessor.speakers(contents, options);
+}
+
+/**
+ * Checks that a transcript's timestamps are in chronological order without
+ * adjusting or writing anything (`update --check`).
+ * @param args Update arguments; only the file and parsing options are used
+ * @returns The lines earlier than the timestamped line before them
+ */
+export async function checkOrder(args: UpdateArgs): Promise<ListedTimestamp[]> {
+  const { contents, options } = await readForParsing(args);
+  return TranscriptProcessor.findOutOfOrder(contents, options);
+}
+
+/**
+ * Reads the input and the parsing options for the read-only modes.
+ */
+async function readForParsing(args: UpdateArgs): Promise<{ contents: string; options: AdjustOptions }> {
+  const fps = args.fps !== undefined ? validateFps(args.fps) : undefined;
+  const skipLines = args.skipLines !== undefined ? validateCount('--skip-lines', args.skipLines) : undefined;
+  const maxLineBytes =
+    args.maxLineBytes !== undefined ? validateCount('--max-line-bytes', args.maxLineBytes, 1) : undefined;
+  const outOfOrderTolerance = args.oooTolerance !== undefined ? validateTolerance(args.oooTolerance) : undefined;
+
+  if (!(await fileExists(args.file))) {
+    throw UpdateError.fileNotFound(args.file);
+  }
+
+  const contents = await readInput(args.file);
+  return {
+    contents,
+    options: {
+      fps,
+      dualTimestamps: args.dualTimestamps,
+      skipLines,
+      commentPrefix: args.commentPrefix,
+      maxLineBytes,
+      outOfOrderTolerance,
+    },
+  };
+}
+
+/**
+ * Updates every transcript directly inside a directory (not recursive), taking
+ * each file's start date and time from its flat-format name. Dotfiles and index
+ * files are skipped; a file whose start cannot be inferred or that fails to
+ * update is reported in `failures` without stopping the rest.
+ * @param args Update arguments with `file` naming the directory
+ * @returns The outcomes and per-file failures
+ */
+export async function executeUpdateDirectory(args: UpdateArgs): Promise<UpdateBatchOutcome> {
+  if (args.time !== undefined) {
+    throw UpdateError.conflictingOptions('--time', 'a directory argument');
+  }
+  if (args.date !== undefined) {
+    throw UpdateError.conflictingOptions('--date', 'a directory argument');
+  }
+  if (args.offset !== undefined) {
+    throw UpdateError.conflictingOptions('--offset', 'a directory argument');
+  }
+  if (args.name !== undefined) {
+    throw UpdateError.conflictingOptions('--name', 'a directory argument');
+  }
+  if (args.autoBase) {
+    throw UpdateError.conflictingOptions('--auto-base', 'a directory argument');
+  }
+  if (args.continueFrom !== undefined) {
+    throw UpdateError.conflictingOptions('--continue-from', 'a directory argument');
+  }
+
+  let entries;
   try {
-    transcript = TranscriptProcessor.adjust(contents, startTime, effectiveDate);
+    entries = await fs.readdir(args.file, { withFileTypes: true });
   } catch (error: any) {
-    throw error;
+    throw UpdateError.ioError(error.message);
   }
 
-  // Resolve output path
-  const outputPath = resolveOutputPath(args, transcript.firstTimestamp, transcript.lastTimestamp);
+  const files = entries
+    .filter((entry) => entry.isFile() && entry.name.endsWith('.txt') && !entry.name.startsWith('.'))
+    .map((entry) => join(args.file, entry.name))
+    .sort();
+
+  const outcomes: UpdateOutcome[] = [];
+  const failures: UpdateFailure[] = [];
+  for (const file of files) {
+    if (await fileStartsWith(file, INDEX_HEADER)) {
+      continue;
+    }
+
+    const start = inferStartFromFilename(file);
+    if (!start) {
+      failures.push({ file, message: UpdateError.uninferableStart(file).message });
+      continue;
+    }
+
+    try {
+      outcomes.push(
+        await executeUpdate({
+          ...args,
+          file,
+          time: format(start.time, TIME_FORMAT),
+          date: format(start.date, DATE_FORMAT_DASHED),
+          autoDate: false,
+          index: undefined,
+        })
+      );
+    } catch (error: any) {
+      failures.push({ file, message: error.message });
+    }
+  }
+
+  if (args.index) {
+    await writeIndex(args.index, outcomes, await writeOptions(args));
+  }
+
+  return { outcomes, failures };
+}
+
+/**
+ * Chooses the exit code for an update run: 1 when any file in a directory
+ * batch failed, so scripts notice, else whatever the warnings call for.
+ * @param result The outcomes and failures of the run
+ * @param failOnWarning Whether warnings should fail the run
+ */
+export function batchExitCode(result: UpdateBatchOutcome, failOnWarning: boolean): number {
+  if (result.failures.length > 0) {
+    return 1;
+  }
+  return warningExitCode(result.outcomes.flatMap((outcome) => outcome.warnings), failOnWarning);
+}
+
+/**
+ * Updates a transcript file, or every transcript in a directory.
+ * @param args Update arguments
+ * @returns The outcomes; a single file yields one outcome and no failures
+ */
+export async function executeUpdateTarget(args: UpdateArgs): Promise<UpdateBatchOutcome> {
+  if (await isDirectory(args.file)) {
+    return executeUpdateDirectory(args);
+  }
+  return { outcomes: [await executeUpdate(args)], failures: [] };
+}
+
+/**
+ * Executes the update operation on a transcript file.
+ * @param args Update arguments
+ * @returns Update outcome
+ */
+export async function executeUpdate(args: UpdateArgs): Promise<UpdateOutcome> {
+  // Validate time and date, taking the base from the input itself for --auto-base
+  // and the date from its name for --auto-date, which overrides --date
+  const autoDate = args.autoDate ? inferAutoDate(args) : undefined;
+  const baseArgs = autoDate !== undefined ? { ...args, date: autoDate, dateInputFormat: undefined } : args;
+  const { startTime, effectiveDate, offset } =
+    args.continueFrom !== undefined
+      ? await resolveContinueBase(baseArgs, args.continueFrom)
+      : resolveBase(
+          baseArgs,
+          args.autoBase ? await readAutoBase(args) : undefined,
+          autoDate !== undefined ? '--auto-date' : '--date'
+        );
+  const fps = args.fps !== undefined ? validateFps(args.fps) : undefined;
+  if (args.round !== undefined && fps === undefined) {
+    // Rounding only matters once timestamps carry sub-second precision
+    throw UpdateError.optionRequires('--round', '--fps');
+  }
+  const round = args.round !== undefined ? validateRound(args.round) : undefined;
+  const minTimestamps =
+    args.minTimestamps !== undefined ? validateCount('--min-timestamps', args.minTimestamps, 1) : undefined;
+  const skipLines = args.skipLines !== undefined ? validateCount('--skip-lines', args.skipLines) : undefined;
+  const maxLineBytes =
+    args.maxLineBytes !== undefined ? validateCount('--max-line-bytes', args.maxLineBytes, 1) : undefined;
+  const preview = args.preview !== undefined ? validateCount('--preview', args.preview, 1) : undefined;
+  const since = args.since !== undefined ? validateBound('--since', args.since) : undefined;
+  const until = args.until !== undefined ? validateBound('--until', args.until) : undefined;
+  const outOfOrderTolerance = args.oooTolerance !== undefined ? validateTolerance(args.oooTolerance) : undefined;
+
+  if (args.flatName !== undefined && args.flatName !== 'start-end' && args.flatName !== 'start') {
+    throw UpdateError.invalidFlatName(args.flatName);
+  }
+  if (args.name !== undefined && (/[\\/]/.test(args.name) || ['', '.', '..'].includes(args.name))) {
+    throw UpdateError.invalidName(args.name);
+  }
+  if (args.layout !== undefined && args.layout !== 'day' && args.layout !== 'iso-week') {
+    throw UpdateError.invalidLayout(args.layout);
+  }
+  if (args.layout === 'iso-week' && args.flat) {
+    throw UpdateError.conflictingOptions('--layout iso-week', '--flat');
+  }
+  const outputEncoding = args.outputEncoding ?? 'utf-8';
+  if (outputEncoding !== 'utf-8' && outputEncoding !== 'utf-16le') {
+    throw UpdateError.invalidOutputEncoding(outputEncoding);
+  }
+  if (args.exclusiveDir && args.flat) {
+    throw UpdateError.conflictingOptions('--exclusive-dir', '--flat');
+  }
+  const outputFormat = args.format ?? 'text';
+  if (outputFormat !== 'text' && outputFormat !== 'jsonl') {
+    throw UpdateError.invalidFormat(outputFormat);
+  }
+  if (outputFormat === 'jsonl' && args.diff) {
+    throw UpdateError.conflictingOptions('--format jsonl', '--diff');
+  }
+  const mergeBase = args.mergeBase ?? 'shared';
+  if (mergeBase !== 'shared' && mergeBase !== 'chained') {
+    throw UpdateError.invalidMergeBase(mergeBase);
+  }
+  const ties = args.ties ?? 'allow';
+  if (ties !== 'allow' && ties !== 'warn' && ties !== 'error') {
+    throw UpdateError.invalidTies(ties);
+  }
+  if (args.verifyDate !== undefined && args.verifyDate !== 'warn' && args.verifyDate !== 'error') {
+    throw UpdateError.invalidVerifyDate(args.verifyDate);
+  }
+  if (args.normalizeSpeakerCase !== undefined && !['title', 'upper', 'lower'].includes(args.normalizeSpeakerCase)) {
+    throw UpdateError.invalidSpeakerCase(args.normalizeSpeakerCase);
+  }
+
+  // Check the inputs exist
+  const inputs = [args.file, ...(args.mergeFiles ?? [])];
+  for (const input of inputs) {
+    if (!(await fileExists(input))) {
+      throw UpdateError.fileNotFound(input);
+    }
+  }
+
+  // Fail fast on an unwritable destination before reading and adjusting the input
+  if (!args.diff) {
+    await assertWritable(args.flat ? process.cwd() : args.outputDir || '.', args);
+  }
+
+  const options: AdjustOptions = {
+    fps,
+    dualTimestamps: args.dualTimestamps,
+    collapseSpeakers: args.collapseSpeakers,
+    speakerCase: args.normalizeSpeakerCase,
+    annotateOriginal: args.annotateOriginal,
+    minTimestamps,
+    skipLines,
+    maxLineBytes,
+    commentPrefix: args.commentPrefix,
+    offset,
+    round,
+    outOfOrderTolerance,
+    since,
+    until,
+    trace: args.trace ? traceTimestamps(args.file) : undefined,
+  };
+
+  // Process the transcript, then append any further inputs being merged
+  const contents = [await readInput(args.file)];
+  const emptyInputs: string[] = [];
+  let transcript: TranscriptUpdate;
+  assertNotEmpty(args.file, contents[0], args);
+  if (contents[0].length === 0) {
+    emptyInputs.push(args.file);
+    transcript = emptyTranscript(startTime, effectiveDate, offset);
+  } else {
+    transcript = TranscriptProcessor.adjust(contents[0], startTime, effectiveDate, options);
+  }
+  for (const input of args.mergeFiles ?? []) {
+    const text = await readInput(input);
+    contents.push(text);
+    assertNotEmpty(input, text, args);
+    if (text.length === 0) {
+      // An allowed empty input adds nothing to the merge
+      emptyInputs.push(input);
+      continue;
+    }
+
+    // A chained input restarts at zero from where the previous one ended
+    const inputOptions = { ...options, trace: args.trace ? traceTimestamps(input) : undefined };
+    const next =
+      mergeBase === 'chained'
+        ? TranscriptProcessor.adjust(text, transcript.lastTimestamp, transcript.lastTimestamp, {
+            ...inputOptions,
+            offset: 0,
+          })
+        : TranscriptProcessor.adjust(text, startTime, effectiveDate, inputOptions);
+    const lineOffset = countLines(transcript.body);
+    transcript = {
+      body: joinSegments([transcript.body, next.body]),
+      firstTimestamp: transcript.firstTimestamp,
+      lastTimestamp: next.lastTimestamp,
+      hasOutOfOrderTimestamps:
+        transcript.hasOutOfOrderTimestamps ||
+        next.hasOutOfOrderTimestamps ||
+        next.firstTimestamp.getTime() < transcript.lastTimestamp.getTime() - (outOfOrderTolerance ?? 0),
+      hasTiedTimestamps:
+        transcript.hasTiedTimestamps ||
+        next.hasTiedTimestamps ||
+        next.firstTimestamp.getTime() === transcript.lastTimestamp.getTime(),
+      malformedLines: transcript.malformedLines + next.malformedLines,
+      timestamps: [
+        ...transcript.timestamps,
+        ...next.timestamps.map((entry) => ({ ...entry, lineNumber: entry.lineNumber + lineOffset })),
+      ],
+    };
+  }
+
+  if (args.trim) {
+    transcript = trimBlankLines(transcript);
+  }
+
+  if (transcript.hasTiedTimestamps && ties === 'error') {
+    throw UpdateError.tiedTimestamps();
+  }
+
+  // The output is dated by the last timestamp, which midnight overflow can move past the supplied date
+  const outputDate = format(transcript.lastTimestamp, DATE_FORMAT_DASHED);
+  const suppliedDate = format(effectiveDate, DATE_FORMAT_DASHED);
+  const dateMismatch = args.verifyDate !== undefined && outputDate !== suppliedDate;
+  if (dateMismatch && args.verifyDate === 'error') {
+    throw UpdateError.dateMismatch(outputDate, suppliedDate);
+  }
+
+  // Resolve output path; JSON Lines output is named .jsonl unless named explicitly
+  let outputPath = resolveOutputPath(args, transcript.firstTimestamp, transcript.lastTimestamp);
+  if (outputFormat === 'jsonl' && args.name === undefined) {
+    outputPath = outputPath.substring(0, outputPath.length - extname(outputPath).length) + JSONL_EXTENSION;
+  }
+
+  const warnings: Warning[] = [];
+  if (autoDate !== undefined && args.date !== undefined && overridesDate(autoDate, args)) {
+    warnings.push(Warning.dateOverridden(autoDate, args.date));
+  }
+  if (transcript.hasOutOfOrderTimestamps) {
+    warnings.push(Warning.outOfOrderTimestamps());
+  }
+  if (transcript.hasTiedTimestamps && ties === 'warn') {
+    warnings.push(Warning.tiedTimestamps());
+  }
+  if (dateMismatch) {
+    warnings.push(Warning.dateMismatch(outputDate, suppliedDate));
+  }
+  if (transcript.malformedLines > 0) {
+    warnings.push(Warning.malformedLines(transcript.malformedLines));
+  }
+  for (const input of emptyInputs) {
+    warnings.push(Warning.emptyInput(input));
+  }
+  // A lone timestamp (often on the last line) names the output HHMMSS-HHMMSS with equal times
+  if (transcript.timestamps.length > 0 && transcript.firstTimestamp.getTime() === transcript.lastTimestamp.getTime()) {
+    warnings.push(Warning.zeroLengthSpan());
+  }
+  if (args.logFile) {
+    try {
+      await appendWarningLog(args.logFile, args.file, warnings);
+    } catch (error: any) {
+      throw UpdateError.ioError(error.message);
+    }
+  }
+
+  // Preview the changes instead of writing
+  if (args.diff) {
+    return {
+      outputPath,
+      firstTimestamp: transcript.firstTimestamp,
+      lastTimestamp: transcript.lastTimestamp,
+      hasOutOfOrderTimestamps: transcript.hasOutOfOrderTimestamps,
+      warnings,
+      diff: unifiedDiff(joinSegments(contents), transcript.body, args.file, outputPath),
+    };
+  }
+
+  // The date directories under the root are only known now; check the one written to.
+  // A pipe or device is written in place and keeps no modification time of ours.
+  if (await isSpecialFile(outputPath)) {
+    if (args.preserveMtime) {
+      throw UpdateError.specialOutput('--preserve-mtime', outputPath);
+    }
+  } else {
+    await assertWritable(dirname(outputPath), args);
+  }
+
+  if (args.exclusiveDir) {
+    await checkExclusiveDir(dirname(outputPath));
+  }
 
   // Write output atomically
+  const body = outputFormat === 'jsonl' ? formatJsonLines(transcript, options) : transcript.body;
   try {
-    await atomicWrite(outputPath, transcript.body);
+    await atomicWrite(outputPath, body, { ...(await writeOptions(args)), encoding: outputEncoding });
+    // Date the output by the end of the recording, so listings sort chronologically
+    if (args.preserveMtime) {
+      await fs.utimes(outputPath, new Date(), transcript.lastTimestamp);
+    }
   } catch (error: any) {
     throw UpdateError.ioError(error.message);
   }
 
-  return {
+  const outcome: UpdateOutcome = {
     outputPath,
+    firstTimestamp: transcript.firstTimestamp,
+    lastTimestamp: transcript.lastTimestamp,
     hasOutOfOrderTimestamps: transcript.hasOutOfOrderTimestamps,
+    warnings,
+    preview: preview !== undefined ? previewLines(transcript.body, preview) : undefined,
   };
+
+  if (args.timestampIndex) {
+    outcome.timestampIndexPath = timestampIndexPath(outputPath);
+    await writeTimestampIndex(outcome.timestampIndexPath, transcript.timestamps, await writeOptions(args));
+  }
+
+  if (args.checksum) {
+    const bytes = encodeContent(body, { encoding: outputEncoding });
+    outcome.checksumPath = await writeChecksumSidecar(outputPath, bytes, await writeOptions(args));
+  }
+
+  if (args.index) {
+    await writeIndex(args.index, [outcome], await writeOptions(args));
+  }
+
+  return outcome;
 }
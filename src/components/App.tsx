@@ -7,6 +7,9 @@ import { Text, Box } from 'ink';
 import { AppProps } from '../types.js';
 import UpdateCommand from './UpdateCommand.js';
 import MergeCommand from './MergeCommand.js';
+import RenameCommand from './RenameCommand.js';
+import DiffCommand from './DiffCommand.js';
+import CatCommand from './CatCommand.js';
 
 const App: React.FC<AppProps> = ({ command, args, flags }) => {
   // No command provided, show help
@@ -19,18 +22,114 @@ const App: React.FC<AppProps> = ({ command, args, flags }) => {
         <Text>  $ plaud-tm &lt;command&gt; [options]</Text>
         <Text> </Text>
         <Text bold>Commands:</Text>
-        <Text>  update &lt;file&gt;      Update timestamps in transcript</Text>
-        <Text>  merge &lt;patterns&gt;   Merge multiple transcripts</Text>
+        <Text>  update &lt;file&gt;      Update timestamps in transcript (or each in a directory)</Text>
+        <Text>  update &lt;files&gt; --merge  Adjust several segments into one output</Text>
+        <Text>  merge &lt;patterns&gt;   Merge multiple transcripts (files, directories, or globs)</Text>
+        <Text>  rename &lt;patterns&gt;  Move transcripts to the canonical name for their timestamps</Text>
+        <Text>  diff &lt;a&gt; &lt;b&gt;       Compare two transcripts line by line by timestamp</Text>
+        <Text>  cat &lt;patterns&gt;     Concatenate files in the order given, without timestamp logic</Text>
+        <Text> </Text>
+        <Text bold>Global options:</Text>
+        <Text>  --forward-slashes  Print paths with '/' on every platform</Text>
+        <Text>  --temp-dir &lt;dir&gt;   Directory for temporary files during atomic writes</Text>
+        <Text>  --retries &lt;n&gt;      Retry transient write errors n times with backoff</Text>
+        <Text>  --chown &lt;u:g&gt;      Give written files and new directories this owner (Unix)</Text>
+        <Text>  --log-file &lt;file&gt;  Also append warnings to file</Text>
+        <Text>  --no-create-dirs   Fail instead of creating a missing output directory</Text>
+        <Text>  --no-fsync         Skip flushing written files to disk, for speed</Text>
+        <Text>  --fsync-dir        Also flush the output directory after each rename</Text>
+        <Text>  --fail-on-warning  Exit with status 1 if any warning was produced</Text>
         <Text> </Text>
         <Text bold>Options for update:</Text>
-        <Text>  --time &lt;time&gt;      Start time (HH:MM:SS) [required]</Text>
-        <Text>  --date &lt;date&gt;      Start date (YYYY-MM-DD) [required]</Text>
+        <Text>  --time &lt;time&gt;      Start time (HH:MM:SS or "YYYY-MM-DD HH:MM:SS") [required unless --offset]</Text>
+        <Text>  --date &lt;date&gt;      Start date (YYYY-MM-DD) [required unless --time has a date]</Text>
+        <Text>  --date-input-format &lt;f&gt; Also accept --date as MM/DD/YYYY or DD/MM/YYYY</Text>
         <Text>  --output-dir &lt;dir&gt; Output directory prefix</Text>
         <Text>  --flat             Use flat format</Text>
+        <Text>  --flat-name &lt;n&gt;    Flat name: start-end (default) or start</Text>
+        <Text>  --name &lt;file&gt;      Use this filename, keeping the date directories</Text>
+        <Text>  --output-encoding &lt;e&gt; Write utf-8 (default) or utf-16le with a BOM</Text>
+        <Text>  --format &lt;f&gt;       Write text (default) or jsonl, one JSON object per line</Text>
+        <Text>  --exclusive-dir    Fail if the date directory already has .txt files</Text>
+        <Text>  --preserve-mtime   Set the output's mtime to its last timestamp</Text>
+        <Text>  --layout &lt;l&gt;       Nested directories: day (YYYY/MM/DD, default) or iso-week (YYYY/Www)</Text>
+        <Text>  --fps &lt;n&gt;          Read HH:MM:SS:FF timestamps at this frame rate</Text>
+        <Text>  --dual-timestamps  Adjust both start and end timestamps on each line</Text>
+        <Text>  --collapse-speakers Merge consecutive lines from the same speaker</Text>
+        <Text>  --normalize-speaker-case &lt;c&gt; Recase speaker labels: title, upper, or lower</Text>
+        <Text>  --diff             Print a unified diff instead of writing</Text>
+        <Text>  --annotate-original Keep the original time, e.g. 18:01:13 (00:00:01)</Text>
+        <Text>  --min-timestamps &lt;n&gt; Fail if fewer than n lines have timestamps (default 1)</Text>
+        <Text>  --comment-prefix &lt;p&gt; Never adjust lines starting with p, e.g. //</Text>
+        <Text>  --merge            Adjust all given files into one output</Text>
+        <Text>  --merge-base &lt;b&gt;   shared (same start for every file) or chained (default shared)</Text>
+        <Text>  --skip-lines &lt;n&gt;   Copy the first n lines through without adjusting them</Text>
+        <Text>  --max-line-bytes &lt;n&gt; Fail on any line longer than n bytes (default 1048576)</Text>
+        <Text>  --index &lt;file&gt;     Write an index of outputs and their spans</Text>
+        <Text>  --trim             Drop blank lines at the start and end of the output</Text>
+        <Text>  --preview &lt;n&gt;      Also print the first n adjusted lines after writing</Text>
+        <Text>  --allow-empty      Write an empty output for an empty input instead of failing</Text>
+        <Text>  --timestamp-index  Also write each timestamp and its line to &lt;output&gt;.idx</Text>
+        <Text>  --checksum         Also write &lt;output&gt;.sha256 for sha256sum -c</Text>
+        <Text>  --offset &lt;dur&gt;     Shift absolute timestamps by [+|-]HH:MM:SS instead of --time</Text>
+        <Text>  --auto-base        Base times on the first timestamp instead of --time</Text>
+        <Text>  --auto-date        Take the date from a YYYYMMDD_HHMMSS_HHMMSS.txt name (overrides --date)</Text>
+        <Text>  --continue-from &lt;f&gt; Start where the updated transcript f ends instead of --time</Text>
+        <Text>  --trace            Print each original -&gt; adjusted timestamp to stderr</Text>
+        <Text>  --round &lt;unit&gt;     Snap timestamps to the nearest unit, e.g. 1s (requires --fps)</Text>
+        <Text>  --ties &lt;policy&gt;    Equal consecutive timestamps: allow (default), warn, or error</Text>
+        <Text>  --ooo-tolerance &lt;d&gt; Only flag timestamps more than HH:MM:SS behind the previous</Text>
+        <Text>  --verify-date &lt;p&gt;  warn or error if the output date differs from --date</Text>
+        <Text>  --check            Exit 1 if timestamps are out of order; writes nothing</Text>
+        <Text>  --list-only        Print the detected timestamps without writing</Text>
+        <Text>  --count-only       Print only the number of timestamped lines</Text>
+        <Text>  --speakers-only    Print the distinct speaker labels in order of appearance</Text>
+        <Text>  --since &lt;time&gt;     Drop lines before HH:MM:SS (or +HH:MM:SS into the input)</Text>
+        <Text>  --until &lt;time&gt;     Drop lines from HH:MM:SS on (or +HH:MM:SS into the input)</Text>
         <Text> </Text>
         <Text bold>Options for merge:</Text>
         <Text>  --output &lt;file&gt;    Output file path</Text>
+        <Text>  --output-location &lt;loc&gt; Inferred output in auto, nested, or cwd (default auto)</Text>
+        <Text>  --output-root &lt;dir&gt; Write one output per day under dir/YYYY/MM/DD</Text>
+        <Text>  --base-dir &lt;dir&gt;   Resolve relative patterns and --output against dir</Text>
         <Text>  --no-delete        Keep source files</Text>
+        <Text>  --date-format &lt;fmt&gt; Date format for the inferred output name (default yyyy-MM-dd)</Text>
+        <Text>  --day-dir-format &lt;fmt&gt; Date format of day directory names (e.g. yyyy_MM_dd)</Text>
+        <Text>  --date-header      Start the output with a '# YYYY-MM-DD' line for the segments' date</Text>
+        <Text>  --compress         Gzip the output (implied by an --output ending in .gz)</Text>
+        <Text>  --renumber-speakers Keep speaker numbers from colliding across segments</Text>
+        <Text>  --min-segments &lt;n&gt; Fail if fewer than n segments would be merged (default 1)</Text>
+        <Text>  --expect-delete    Fail if no source files would be deleted</Text>
+        <Text>  --preserve-order   Merge in the order given instead of by time</Text>
+        <Text>  --safe             Refuse to overwrite an output newer than its sources</Text>
+        <Text>  --checksum         Print a SHA-256 of the merged output</Text>
+        <Text>  --verify           Read the output back and check it before deleting sources</Text>
+        <Text>  --hidden           Include dotfiles when matching segments</Text>
+        <Text>  --exclude &lt;glob&gt;   Skip files matching glob (repeatable)</Text>
+        <Text>  --max-depth &lt;n&gt;    Only match files at most n directories below a glob's fixed prefix</Text>
+        <Text>  --start &lt;time&gt;     Name the output &lt;date&gt;_HHMMSS-HHMMSS.txt with this start</Text>
+        <Text>  --end &lt;time&gt;       Name the output &lt;date&gt;_HHMMSS-HHMMSS.txt with this end</Text>
+        <Text>  --group-by-gap &lt;d&gt; Write one output per run of segments with gaps up to d (e.g. 1h)</Text>
+        <Text>  --stream           Stream segments into the output instead of merging in memory</Text>
+        <Text>  --eol &lt;e&gt;          Write every line ending as lf or crlf (default: keep each segment's)</Text>
+        <Text>  --max-output-size &lt;size&gt; Split output into parts of at most this size (e.g. 5M)</Text>
+        <Text>  --json             Print a JSON summary instead of the file list</Text>
+        <Text>  --count-only       Print only the number of segments matched; merges nothing</Text>
+        <Text>  --post-merge-cmd &lt;cmd&gt; Run cmd after merging; {'{output}'} is the output path</Text>
+        <Text> </Text>
+        <Text bold>Options for rename:</Text>
+        <Text>  --date &lt;date&gt;      Date of the times in the files (YYYY-MM-DD) [required]</Text>
+        <Text>  --date-input-format &lt;f&gt; Also accept --date as MM/DD/YYYY or DD/MM/YYYY</Text>
+        <Text>  --output-dir &lt;dir&gt; Output directory prefix</Text>
+        <Text>  --flat             Use flat format</Text>
+        <Text>  --dry-run          Print the moves without making them</Text>
+        <Text> </Text>
+        <Text bold>Options for diff:</Text>
+        <Text>  --tolerance &lt;s&gt;    Pair lines with the same text up to s seconds apart (default 2)</Text>
+        <Text>  --json             Print a JSON report instead of the line list</Text>
+        <Text> </Text>
+        <Text bold>Options for cat:</Text>
+        <Text>  --output &lt;file&gt;    Write to file instead of stdout</Text>
         <Text> </Text>
         <Text bold>Examples:</Text>
         <Text>  $ plaud-tm update transcript.txt --time 18:06:13 --date 2024-03-15</Text>
@@ -45,6 +144,12 @@ const App: React.FC<AppProps> = ({ command, args, flags }) => {
       return <UpdateCommand args={args} flags={flags} />;
     case 'merge':
       return <MergeCommand args={args} flags={flags} />;
+    case 'rename':
+      return <RenameCommand args={args} flags={flags} />;
+    case 'diff':
+      return <DiffCommand args={args} flags={flags} />;
+    case 'cat':
+      return <CatCommand args={args} flags={flags} />;
     default:
       return (
         <Box>
@@ -0,0 +1,91 @@
+/**
+ * CatCommand Ink component for the cat command UI.
+ */
+
+import React, { useEffect, useState } from 'react';
+import { Text, Box } from 'ink';
+import Spinner from 'ink-spinner';
+import { executeCat, CatOutcome } from '../commands/cat.js';
+import { CatArgs } from '../types.js';
+import { displayPath } from '../utils/paths.js';
+
+interface CatCommandProps {
+  args: string[];
+  flags: Record<string, any>;
+}
+
+const CatCommand: React.FC<CatCommandProps> = ({ args, flags }) => {
+  const [status, setStatus] = useState<'validating' | 'processing' | 'success' | 'error'>('validating');
+  const [error, setError] = useState<string | null>(null);
+  const [outcome, setOutcome] = useState<CatOutcome | null>(null);
+
+  useEffect(() => {
+    const run = async () => {
+      try {
+        // Validate required arguments
+        if (args.length === 0) {
+          setError('Missing required argument: <patterns>');
+          process.exitCode = 1;
+          setStatus('error');
+          return;
+        }
+
+        // Build CatArgs
+        const catArgs: CatArgs = {
+          patterns: args,
+          output: flags.output,
+          tempDir: flags.tempDir,
+          noCreateDirs: flags.createDirs === false, // meow converts --no-create-dirs to createDirs: false
+          noFsync: flags.fsync === false,
+          fsyncDir: flags.fsyncDir || false,
+          retries: flags.retries,
+        };
+
+        setStatus('processing');
+
+        // Execute cat
+        setOutcome(await executeCat(catArgs));
+        setStatus('success');
+      } catch (err: any) {
+        setError(err.message || 'An unknown error occurred');
+        process.exitCode = 1;
+        setStatus('error');
+      }
+    };
+
+    run();
+  }, [args, flags]);
+
+  if (status === 'error') {
+    return (
+      <Box flexDirection="column">
+        <Text color="red" bold>Error:</Text>
+        <Text color="red">{error}</Text>
+      </Box>
+    );
+  }
+
+  // Without --output the content itself goes to stdout, so print nothing around it
+  if (!flags.output) {
+    return null;
+  }
+
+  if (status === 'validating' || status === 'processing') {
+    return (
+      <Box>
+        <Text color="cyan">
+          <Spinner type="dots" />
+        </Text>
+        <Text> {status === 'validating' ? 'Validating...' : 'Concatenating files...'}</Text>
+      </Box>
+    );
+  }
+
+  return (
+    <Text color="green">
+      Wrote {displayPath(flags.output, flags.forwardSlashes)} ({outcome?.files.length ?? 0} files)
+    </Text>
+  );
+};
+
+export default CatCommand;
@@ -0,0 +1,101 @@
+/**
+ * DiffCommand Ink component for the diff command UI.
+ */
+
+import React, { useEffect, useState } from 'react';
+import { Text, Box } from 'ink';
+import Spinner from 'ink-spinner';
+import { executeDiff, formatDiffJson, formatDiffReport, hasDifferences, DiffOutcome } from '../commands/diff.js';
+import { DiffArgs } from '../types.js';
+
+interface DiffCommandProps {
+  args: string[];
+  flags: Record<string, any>;
+}
+
+const DiffCommand: React.FC<DiffCommandProps> = ({ args, flags }) => {
+  const [status, setStatus] = useState<'validating' | 'processing' | 'success' | 'error'>('validating');
+  const [error, setError] = useState<string | null>(null);
+  const [outcome, setOutcome] = useState<DiffOutcome | null>(null);
+
+  useEffect(() => {
+    const run = async () => {
+      try {
+        // Validate required arguments
+        if (args.length !== 2) {
+          setError('Expected exactly two arguments: <a> <b>');
+          process.exitCode = 2;
+          setStatus('error');
+          return;
+        }
+
+        // Build DiffArgs
+        const diffArgs: DiffArgs = {
+          a: args[0],
+          b: args[1],
+          tolerance: flags.tolerance,
+          json: flags.json || false,
+        };
+
+        setStatus('processing');
+
+        // Execute diff; like diff(1), exit 1 when the transcripts differ
+        const result = await executeDiff(diffArgs);
+        if (hasDifferences(result)) {
+          process.exitCode = 1;
+        }
+        if (flags.json) {
+          // Written raw: Ink would hard-wrap long lines and corrupt the JSON for parsers
+          process.stdout.write(formatDiffJson(result) + '\n');
+        }
+        setOutcome(result);
+        setStatus('success');
+      } catch (err: any) {
+        setError(err.message || 'An unknown error occurred');
+        process.exitCode = 2;
+        setStatus('error');
+      }
+    };
+
+    run();
+  }, [args, flags]);
+
+  // With --json, stdout carries only the JSON, so print nothing around it
+  if (flags.json && status !== 'error') {
+    return null;
+  }
+
+  if (status === 'validating' || status === 'processing') {
+    return (
+      <Box>
+        <Text color="cyan">
+          <Spinner type="dots" />
+        </Text>
+        <Text> {status === 'validating' ? 'Validating...' : 'Comparing transcripts...'}</Text>
+      </Box>
+    );
+  }
+
+  if (status === 'error' || !outcome) {
+    return (
+      <Box flexDirection="column">
+        <Text color="red" bold>Error:</Text>
+        <Text color="red">{error}</Text>
+      </Box>
+    );
+  }
+
+  const report = formatDiffReport(outcome);
+  return (
+    <Box flexDirection="column">
+      {report.map((line, index) => (
+        <Text key={index} color={line.startsWith('-') ? 'red' : line.startsWith('+') ? 'green' : 'yellow'}>
+          {line}
+        </Text>
+      ))}
+      {report.length === 0 && <Text>No differences ({outcome.identical} matching lines)</Text>}
+    </Box>
+  );
+};
+
+export default DiffCommand;
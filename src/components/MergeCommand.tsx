@@ -5,8 +5,16 @@
 import React, { useEffect, useState } from 'react';
 import { Text, Box } from 'ink';
 import Spinner from 'ink-spinner';
-import { executeMerge } from '../commands/merge.js';
+import {
+  executeMerge,
+  executeMergeGroups,
+  countSegments,
+  formatMergeJson,
+  MergeOutcome,
+} from '../commands/merge.js';
 import { MergeArgs } from '../types.js';
+import { displayPath } from '../utils/paths.js';
+import { warningExitCode } from '../warnings.js';
 
 interface MergeCommandProps {
   args: string[];
@@ -16,8 +24,8 @@ interface MergeCommandProps {
 const MergeCommand: React.FC<MergeCommandProps> = ({ args, flags }) => {
   const [status, setStatus] = useState<'validating' | 'processing' | 'success' | 'error'>('validating');
   const [error, setError] = useState<string | null>(null);
-  const [outputPath, setOutputPath] = useState<string | null>(null);
-  const [files, setFiles] = useState<string[]>([]);
+  const [outcomes, setOutcomes] = useState<MergeOutcome[]>([]);
+  const [count, setCount] = useState<number | null>(null);
 
   useEffect(() => {
     const run = async () => {
@@ -33,18 +41,71 @@ const MergeCommand: React.FC<MergeCommandProps> = ({ args, flags }) => {
         const mergeArgs: MergeArgs = {
           patterns: args,
           output: flags.output,
+          baseDir: flags.baseDir,
+          outputLocation: flags.outputLocation,
+          outputRoot: flags.outputRoot,
           noDelete: flags.delete === false, // meow converts --no-delete to delete: false
+          dateFormat: flags.dateFormat,
+          dayDirFormat: flags.dayDirFormat,
+          dateHeader: flags.dateHeader || false,
+          stream: flags.stream || false,
+          eol: flags.eol,
+          checksum: flags.checksum || false,
+          verify: flags.verify || false,
+          safe: flags.safe || false,
+          expectDelete: flags.expectDelete || false,
+          minSegments: flags.minSegments,
+          renumberSpeakers: flags.renumberSpeakers || false,
+          compress: flags.compress || false,
+          preserveOrder: flags.preserveOrder || false,
+          hidden: flags.hidden || false,
+          exclude: flags.exclude,
+          maxDepth: flags.maxDepth,
+          maxOutputSize: flags.maxOutputSize,
+          groupByGap: flags.groupByGap,
+          start: flags.start,
+          end: flags.end,
+          postMergeCmd: flags.postMergeCmd,
+          tempDir: flags.tempDir,
+          noCreateDirs: flags.createDirs === false, // meow converts --no-create-dirs to createDirs: false
+          noFsync: flags.fsync === false,
+          fsyncDir: flags.fsyncDir || false,
+          chown: flags.chown,
+          logFile: flags.logFile,
+          retries: flags.retries,
         };
 
         setStatus('processing');
 
-        // Execute merge
-        const result = await executeMerge(mergeArgs);
-        setFiles(result.files);
-        setOutputPath(result.outputPath);
+        if (flags.countOnly) {
+          setCount(await countSegments(mergeArgs));
+          setStatus('success');
+          return;
+        }
+
+        // Execute merge, once per group when grouping by gap or by day
+        const grouped = mergeArgs.groupByGap !== undefined || mergeArgs.outputRoot !== undefined;
+        const results = grouped ? await executeMergeGroups(mergeArgs) : [await executeMerge(mergeArgs)];
+        setOutcomes(results);
+        if (flags.json) {
+          // Written raw: Ink would hard-wrap long lines and corrupt the JSON for parsers
+          process.stdout.write(formatMergeJson(grouped ? results : results[0]) + '\n');
+        }
+        const warningCode = warningExitCode(
+          results.flatMap((result) => result.warnings),
+          flags.failOnWarning || false
+        );
+        if (warningCode !== 0) {
+          process.exitCode = warningCode;
+        }
+        const failedHook = results.find((result) => result.postMergeStatus);
+        if (failedHook) {
+          process.exitCode = failedHook.postMergeStatus;
+        }
         setStatus('success');
       } catch (err: any) {
         setError(err.message || 'An unknown error occurred');
+        process.exitCode = 1;
         setStatus('error');
       }
     };
@@ -52,6 +113,11 @@ const MergeCommand: React.FC<MergeCommandProps> = ({ args, flags }) => {
     run();
   }, [args, flags]);
 
+  // With --json, stdout carries only the JSON, so print nothing around it
+  if (flags.json && status !== 'error' && count === null) {
+    return null;
+  }
+
   if (status === 'validating' || status === 'processing') {
     return (
       <Box>
@@ -72,12 +138,56 @@ const MergeCommand: React.FC<MergeCommandProps> = ({ args, flags }) => {
     );
   }
 
+  if (count !== null) {
+    return <Text>{count}</Text>;
+  }
+
   return (
     <Box flexDirection="column">
-      {files.map((file) => (
-        <Text key={file}>{file}</Text>
+      {outcomes.map(({ outputPath, parts, files, segments, lines, warnings, checksum, postMergeStatus }) => (
+        <Box key={outputPath} flexDirection="column">
+          {warnings.map((warning, index) => (
+            <Text key={index} color="yellow">Warning: {warning.message}</Text>
+          ))}
+          {files.map((file) => (
+            <Text key={file}>{displayPath(file, flags.forwardSlashes)}</Text>
+          ))}
+          {parts.length > 1 ? (
+            <>
+              <Text color="green">
+                Merged {segments} segments ({lines} lines) into {parts.length} parts:
+              </Text>
+              {parts.map((part) => (
+                <Text key={part} color="green">
+                  {displayPath(part, flags.forwardSlashes)}
+                </Text>
+              ))}
+            </>
+          ) : (
+            <Text color="green">
+              Merged {segments} segments ({lines} lines) into {displayPath(outputPath, flags.forwardSlashes)}
+            </Text>
+          )}
+          {checksum &&
+            (checksum.sha256.length === 1 ? (
+              <Text>
+                SHA-256 {checksum.sha256[0]} ({checksum.sourceBytes} source bytes)
+              </Text>
+            ) : (
+              <>
+                {checksum.sha256.map((hash, index) => (
+                  <Text key={parts[index]}>
+                    SHA-256 {hash} {displayPath(parts[index], flags.forwardSlashes)}
+                  </Text>
+                ))}
+                <Text>({checksum.sourceBytes} source bytes)</Text>
+              </>
+            ))}
+          {postMergeStatus !== undefined && postMergeStatus !== 0 && (
+            <Text color="red">Post-merge command exited with status {postMergeStatus}</Text>
+          )}
+        </Box>
       ))}
-      <Text color="green">Merged into {outputPath}</Text>
     </Box>
   );
 };
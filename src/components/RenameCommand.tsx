@@ -0,0 +1,91 @@
+/**
+ * RenameCommand Ink component for the rename command UI.
+ */
+
+import React, { useEffect, useState } from 'react';
+import { Text, Box } from 'ink';
+import Spinner from 'ink-spinner';
+import { executeRename, RenameOutcome } from '../commands/rename.js';
+import { RenameArgs } from '../types.js';
+import { displayPath } from '../utils/paths.js';
+
+interface RenameCommandProps {
+  args: string[];
+  flags: Record<string, any>;
+}
+
+const RenameCommand: React.FC<RenameCommandProps> = ({ args, flags }) => {
+  const [status, setStatus] = useState<'validating' | 'processing' | 'success' | 'error'>('validating');
+  const [error, setError] = useState<string | null>(null);
+  const [outcome, setOutcome] = useState<RenameOutcome | null>(null);
+
+  useEffect(() => {
+    const run = async () => {
+      try {
+        // Validate required arguments
+        if (args.length === 0) {
+          setError('Missing required argument: <patterns>');
+          setStatus('error');
+          return;
+        }
+
+        // Build RenameArgs
+        const renameArgs: RenameArgs = {
+          patterns: args,
+          date: flags.date,
+          dateInputFormat: flags.dateInputFormat,
+          outputDir: flags.outputDir,
+          flat: flags.flat || false,
+          dryRun: flags.dryRun || false,
+        };
+
+        setStatus('processing');
+
+        // Execute rename
+        setOutcome(await executeRename(renameArgs));
+        setStatus('success');
+      } catch (err: any) {
+        setError(err.message || 'An unknown error occurred');
+        process.exitCode = 1;
+        setStatus('error');
+      }
+    };
+
+    run();
+  }, [args, flags]);
+
+  if (status === 'validating' || status === 'processing') {
+    return (
+      <Box>
+        <Text color="cyan">
+          <Spinner type="dots" />
+        </Text>
+        <Text> {status === 'validating' ? 'Validating...' : 'Renaming transcripts...'}</Text>
+      </Box>
+    );
+  }
+
+  if (status === 'error') {
+    return (
+      <Box flexDirection="column">
+        <Text color="red" bold>Error:</Text>
+        <Text color="red">{error}</Text>
+      </Box>
+    );
+  }
+
+  const moves = outcome?.moves ?? [];
+  return (
+    <Box flexDirection="column">
+      {moves.map(({ source, target }) => (
+        <Text key={source} color={outcome?.dryRun ? undefined : 'green'}>
+          {outcome?.dryRun ? 'Would move' : 'Moved'} {displayPath(source, flags.forwardSlashes)} to{' '}
+          {displayPath(target, flags.forwardSlashes)}
+        </Text>
+      ))}
+      {moves.length === 0 && <Text>All files are already in place</Text>}
+    </Box>
+  );
+};
+
+export default RenameCommand;
@@ -5,8 +5,19 @@
 import React, { useEffect, useState } from 'react';
 import { Text, Box } from 'ink';
 import Spinner from 'ink-spinner';
-import { executeUpdate } from '../commands/update.js';
+import {
+  executeUpdateTarget,
+  listTimestamps,
+  countTimestamps,
+  listSpeakers,
+  checkOrder,
+  batchExitCode,
+  UpdateOutcome,
+  UpdateFailure,
+} from '../commands/update.js';
+import { TimestampListing, ListedTimestamp } from '../transcript.js';
 import { UpdateArgs } from '../types.js';
+import { displayPath } from '../utils/paths.js';
 
 interface UpdateCommandProps {
   args: string[];
@@ -16,8 +27,12 @@ interface UpdateCommandProps {
 const UpdateCommand: React.FC<UpdateCommandProps> = ({ args, flags }) => {
   const [status, setStatus] = useState<'validating' | 'processing' | 'success' | 'error'>('validating');
   const [error, setError] = useState<string | null>(null);
-  const [outputPath, setOutputPath] = useState<string | null>(null);
-  const [hasWarning, setHasWarning] = useState(false);
+  const [outcomes, setOutcomes] = useState<UpdateOutcome[]>([]);
+  const [failures, setFailures] = useState<UpdateFailure[]>([]);
+  const [listing, setListing] = useState<TimestampListing | null>(null);
+  const [outOfOrder, setOutOfOrder] = useState<ListedTimestamp[] | null>(null);
+  const [count, setCount] = useState<number | null>(null);
+  const [speakers, setSpeakers] = useState<string[] | null>(null);
 
   useEffect(() => {
     const run = async () => {
@@ -29,36 +44,110 @@ const UpdateCommand: React.FC<UpdateCommandProps> = ({ args, flags }) => {
           return;
         }
 
-        if (!flags.time) {
-          setError('Missing required option: --time <time>');
-          setStatus('error');
-          return;
-        }
-
-        if (!flags.date) {
-          setError('Missing required option: --date <date>');
-          setStatus('error');
-          return;
-        }
-
         // Build UpdateArgs
         const updateArgs: UpdateArgs = {
           file: args[0],
+          mergeFiles: flags.merge ? args.slice(1) : undefined,
+          mergeBase: flags.mergeBase,
+          ties: flags.ties,
+          oooTolerance: flags.oooTolerance,
+          verifyDate: flags.verifyDate,
           time: flags.time,
           date: flags.date,
+          dateInputFormat: flags.dateInputFormat,
           outputDir: flags.outputDir,
           flat: flags.flat || false,
+          flatName: flags.flatName,
+          layout: flags.layout,
+          name: flags.name,
+          exclusiveDir: flags.exclusiveDir || false,
+          preserveMtime: flags.preserveMtime || false,
+          outputEncoding: flags.outputEncoding,
+          format: flags.format,
+          fps: flags.fps,
+          dualTimestamps: flags.dualTimestamps || false,
+          collapseSpeakers: flags.collapseSpeakers || false,
+          normalizeSpeakerCase: flags.normalizeSpeakerCase,
+          diff: flags.diff || false,
+          annotateOriginal: flags.annotateOriginal || false,
+          minTimestamps: flags.minTimestamps,
+          skipLines: flags.skipLines,
+          maxLineBytes: flags.maxLineBytes,
+          commentPrefix: flags.commentPrefix,
+          index: flags.index,
+          timestampIndex: flags.timestampIndex || false,
+          checksum: flags.checksum || false,
+          allowEmpty: flags.allowEmpty || false,
+          preview: flags.preview,
+          trim: flags.trim || false,
+          offset: flags.offset,
+          autoBase: flags.autoBase || false,
+          autoDate: flags.autoDate || false,
+          trace: flags.trace || false,
+          continueFrom: flags.continueFrom,
+          tempDir: flags.tempDir,
+          noCreateDirs: flags.createDirs === false, // meow converts --no-create-dirs to createDirs: false
+          noFsync: flags.fsync === false,
+          fsyncDir: flags.fsyncDir || false,
+          chown: flags.chown,
+          logFile: flags.logFile,
+          retries: flags.retries,
+          round: flags.round,
+          since: flags.since,
+          until: flags.until,
         };
 
         setStatus('processing');
 
-        // Execute update
-        const result = await executeUpdate(updateArgs);
-        setOutputPath(result.outputPath);
-        setHasWarning(result.hasOutOfOrderTimestamps);
+        if (flags.check) {
+          const offending = await checkOrder(updateArgs);
+          for (const entry of offending) {
+            process.stderr.write(`line ${entry.lineNumber}: ${entry.timestamp} ${entry.text}\n`);
+          }
+          if (offending.length > 0) {
+            process.exitCode = 1;
+          }
+          setOutOfOrder(offending);
+          setStatus('success');
+          return;
+        }
+
+        if (flags.countOnly) {
+          setCount(await countTimestamps(updateArgs));
+          setStatus('success');
+          return;
+        }
+
+        if (flags.speakersOnly) {
+          setSpeakers(await listSpeakers(updateArgs));
+          setStatus('success');
+          return;
+        }
+
+        if (flags.listOnly) {
+          setListing(await listTimestamps(updateArgs));
+          setStatus('success');
+          return;
+        }
+
+        // Execute update on the file, or on each transcript in a directory
+        const result = await executeUpdateTarget(updateArgs);
+        for (const outcome of result.outcomes) {
+          // Written raw: Ink would hard-wrap long lines and break the patch
+          if (outcome.diff) {
+            process.stdout.write(outcome.diff);
+          }
+        }
+        setOutcomes(result.outcomes);
+        setFailures(result.failures);
+        const exitCode = batchExitCode(result, flags.failOnWarning || false);
+        if (exitCode !== 0) {
+          process.exitCode = exitCode;
+        }
         setStatus('success');
       } catch (err: any) {
         setError(err.message || 'An unknown error occurred');
+        process.exitCode = 1;
         setStatus('error');
       }
     };
@@ -86,12 +175,73 @@ const UpdateCommand: React.FC<UpdateCommandProps> = ({ args, flags }) => {
     );
   }
 
+  if (outOfOrder !== null) {
+    return outOfOrder.length === 0 ? (
+      <Text color="green">Timestamps are in chronological order</Text>
+    ) : (
+      <Text color="red">{outOfOrder.length} line(s) out of chronological order</Text>
+    );
+  }
+
+  if (count !== null) {
+    return <Text>{count}</Text>;
+  }
+
+  if (speakers !== null) {
+    return (
+      <Box flexDirection="column">
+        {speakers.map((speaker) => (
+          <Text key={speaker}>{speaker}</Text>
+        ))}
+      </Box>
+    );
+  }
+
+  if (listing !== null) {
+    return (
+      <Box flexDirection="column">
+        {listing.timestamps.map((entry) => (
+          <Text key={entry.lineNumber}>
+            {String(entry.lineNumber).padStart(6)}  {entry.timestamp}  {entry.text}
+          </Text>
+        ))}
+        <Text>
+          {listing.timestamps.length} timestamped, {listing.plainLines} plain line(s)
+        </Text>
+      </Box>
+    );
+  }
+
   return (
     <Box flexDirection="column">
-      {hasWarning && (
-        <Text color="yellow">Warning: timestamps in input were not in chronological order</Text>
-      )}
-      <Text color="green">Wrote {outputPath}</Text>
+      {outcomes.map((outcome) => (
+        <Box key={outcome.outputPath} flexDirection="column">
+          {outcome.warnings.map((warning, index) => (
+            <Text key={index} color="yellow">Warning: {warning.message}</Text>
+          ))}
+          {outcome.diff !== undefined ? (
+            outcome.diff.length === 0 && <Text>No changes</Text>
+          ) : (
+            <Text color="green">Wrote {displayPath(outcome.outputPath, flags.forwardSlashes)}</Text>
+          )}
+          {outcome.preview?.map((line, index) => (
+            <Text key={`preview-${index}`} dimColor>
+              {line}
+            </Text>
+          ))}
+          {outcome.timestampIndexPath && (
+            <Text color="green">Wrote {displayPath(outcome.timestampIndexPath, flags.forwardSlashes)}</Text>
+          )}
+          {outcome.checksumPath && (
+            <Text color="green">Wrote {displayPath(outcome.checksumPath, flags.forwardSlashes)}</Text>
+          )}
+        </Box>
+      ))}
+      {failures.map((failure) => (
+        <Text key={failure.file} color="red">
+          Failed {displayPath(failure.file, flags.forwardSlashes)}: {failure.message}
+        </Text>
+      ))}
     </Box>
   );
 };
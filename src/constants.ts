@@ -5,9 +5,15 @@
 /** Maximum file size in bytes (10 MB) to prevent OOM on large files. */
 export const MAX_FILE_SIZE = 10 * 1024 * 1024;
 
+/** Default maximum line length in bytes (1 MB); longer lines are almost certainly not a transcript. */
+export const MAX_LINE_BYTES = 1024 * 1024;
+
 /** Time format for parsing and display (HH:MM:SS). */
 export const TIME_FORMAT = 'HH:mm:ss';
 
+/** Time format for output filenames (HHmmss). */
+export const TIME_FORMAT_COMPACT = 'HHmmss';
+
 /** Date format for directory names (yyyy-MM-dd). */
 export const DATE_FORMAT_DASHED = 'yyyy-MM-dd';
 
@@ -23,5 +29,70 @@ export const MONTH_FORMAT = 'MM';
 /** Day format for nested directories. */
 export const DAY_FORMAT = 'dd';
 
-/** Regex pattern to match timestamp lines (HH:MM:SS format). */
-export const TIMESTAMP_REGEX = /^(\d{2}:\d{2}:\d{2})\s+(.*)$/;
+/** ISO week-numbering year for the iso-week layout (differs from the calendar year around New Year). */
+export const ISO_WEEK_YEAR_FORMAT = 'RRRR';
+
+/** ISO week directory for the iso-week layout, e.g. W01. */
+export const ISO_WEEK_FORMAT = "'W'II";
+
+/** Date-time format for spans written to index files. */
+export const DATETIME_FORMAT = 'yyyy-MM-dd HH:mm:ss';
+
+/** ISO 8601 local date-time format for `update --format jsonl` entries. */
+export const ISO_DATETIME_FORMAT = "yyyy-MM-dd'T'HH:mm:ss";
+
+/** Date formats accepted by `--date-input-format`, mapped to their date-fns patterns. */
+export const DATE_INPUT_FORMATS: Record<string, string> = {
+  'YYYY-MM-DD': 'yyyy-MM-dd',
+  'MM/DD/YYYY': 'MM/dd/yyyy',
+  'DD/MM/YYYY': 'dd/MM/yyyy',
+  'DD.MM.YYYY': 'dd.MM.yyyy',
+};
+
+/** First line of an output index file; merge skips files starting with it. */
+export const INDEX_HEADER = '# plaud-tm index';
+
+/** Regex pattern to match timestamp lines (HH:MM:SS format; hours may run past 99). */
+export const TIMESTAMP_REGEX = /^(\d{2,}:\d{2}:\d{2})\s+(.*)$/;
+
+/** Regex pattern to match a leading per-line date prefix (`YYYY-MM-DD `). */
+export const DATE_PREFIX_REGEX = /^\d{4}-\d{2}-\d{2} /;
+
+/** Regex pattern to match the spaces and tabs indenting a timestamp line. */
+export const LEADING_WHITESPACE_REGEX = /^[ \t]*/;
+
+/** Regex pattern to match an original-time annotation after a timestamp, e.g. ` (00:00:01)`. */
+export const ANNOTATION_REGEX = /^ \(\d{2,}:\d{2}:\d{2}(?::\d{2})?\)/;
+
+/** Extension of the timestamps-only index written next to an update output. */
+export const TIMESTAMP_INDEX_EXTENSION = '.idx';
+
+/** Extension added to an update output's name for its `--checksum` sidecar; merge never reads these. */
+export const CHECKSUM_EXTENSION = '.sha256';
+
+/** Extension of an update output written with `--format jsonl`. */
+export const JSONL_EXTENSION = '.jsonl';
+
+/** Extension marking gzip-compressed files. */
+export const GZIP_EXTENSION = '.gz';
+
+/** Regex pattern for the names merge reads as segments: `.txt`, or `.txt.gz` when compressed. */
+export const SEGMENT_EXTENSION_REGEX = /\.txt(\.gz)?$/;
+
+/** Regex pattern for numbered Plaud speaker labels (`Speaker 1`); global, captures the number. */
+export const SPEAKER_LABEL_REGEX = /\bSpeaker (\d+)\b/g;
+
+/**
+ * Regex pattern for the speaker label leading the text after a timestamp: words
+ * of letters ending where a `:` or a digit follows, e.g. `speaker` in ` speaker 1: hi`.
+ */
+export const LEADING_SPEAKER_REGEX = /^(\s*)(\p{L}+(?: \p{L}+)*)(?=\s*[:\d])/u;
+
+/** Regex pattern for an elapsed HH:MM:SS time; hours may exceed 23 and take more than two digits. */
+export const ELAPSED_TIME_REGEX = /^(\d{2,}):(\d{2}):(\d{2})$/;
+
+/** Regex pattern for an elapsed HH:MM:SS token at the start of some text. */
+export const ELAPSED_TOKEN_REGEX = /^\d{2,}:\d{2}:\d{2}/;
+
+/** Regex pattern to match frame-based timestamps (HH:MM:SS:FF format). */
+export const FRAME_TIMESTAMP_REGEX = /^(\d{2,}:\d{2}:\d{2}):(\d{2})/;
@@ -12,6 +12,21 @@ export class AppError extends Error {
   }
 }
 
+/**
+ * A file over a size limit. The sizes are kept so each command can report it
+ * with its own error type.
+ */
+export class SizeError extends AppError {
+  constructor(
+    readonly path: string,
+    readonly size: number,
+    readonly max: number
+  ) {
+    super(`File too large: ${size} bytes exceeds maximum of ${max} bytes`);
+    this.name = 'SizeError';
+  }
+}
+
 /**
  * Errors related to transcript processing.
  */
@@ -24,6 +39,22 @@ export class TranscriptError extends AppError {
   static noTimestamps(): TranscriptError {
     return new TranscriptError('No timestamped lines were found in the input file');
   }
+
+  static emptyWindow(): TranscriptError {
+    return new TranscriptError('No timestamped lines fall within the --since/--until window');
+  }
+
+  static lineTooLong(lineNumber: number, bytes: number, max: number): TranscriptError {
+    return new TranscriptError(
+      `Line ${lineNumber} is ${bytes} bytes, over the ${max} byte line limit (see --max-line-bytes)`
+    );
+  }
+
+  static tooFewTimestamps(found: number, min: number): TranscriptError {
+    return new TranscriptError(
+      `Only ${found} timestamped line(s) found, expected at least ${min}; check the input format`
+    );
+  }
 }
 
 /**
@@ -39,23 +70,230 @@ export class UpdateError extends AppError {
     return new UpdateError(`File too large: ${size} bytes exceeds maximum of ${max} bytes`);
   }
 
+  static directoryNotEmpty(dir: string, files: string[]): UpdateError {
+    const listed = files.length > 3 ? `${files.slice(0, 3).join(', ')}, ...` : files.join(', ');
+    return new UpdateError(`${dir} already contains transcripts (${listed}); refusing to write with --exclusive-dir`);
+  }
+
+  static emptyInput(path: string): UpdateError {
+    return new UpdateError(`Input file is empty: ${path}. Use --allow-empty to write an empty output`);
+  }
+
   static invalidTime(value: string): UpdateError {
-    return new UpdateError(`Invalid time '${value}'. Use HH:MM:SS (e.g. 18:06:13)`);
+    return new UpdateError(
+      `Invalid time '${value}'. Use HH:MM:SS (e.g. 18:06:13) or YYYY-MM-DD HH:MM:SS (e.g. 2024-03-15 18:06:13)`
+    );
   }
 
   static invalidDate(value: string): UpdateError {
     return new UpdateError(`Invalid date '${value}'. Use YYYY-MM-DD (e.g. 2024-03-15)`);
   }
 
+  static ambiguousDate(value: string): UpdateError {
+    return new UpdateError(
+      `Ambiguous date '${value}'. Pass --date-input-format MM/DD/YYYY or DD/MM/YYYY, or use YYYY-MM-DD`
+    );
+  }
+
+  static invalidDateInputFormat(value: string): UpdateError {
+    return new UpdateError(
+      `Invalid date input format '${value}'. Use YYYY-MM-DD, MM/DD/YYYY, DD/MM/YYYY, or DD.MM.YYYY`
+    );
+  }
+
+  static invalidOffset(value: string): UpdateError {
+    return new UpdateError(`Invalid offset '${value}'. Use [+|-]HH:MM:SS (e.g. 00:03:00 or -00:00:30)`);
+  }
+
+  static invalidTolerance(value: string): UpdateError {
+    return new UpdateError(`Invalid out-of-order tolerance '${value}'. Use HH:MM:SS (e.g. 00:00:02)`);
+  }
+
+  static invalidBound(option: string, value: string): UpdateError {
+    return new UpdateError(
+      `Invalid ${option} '${value}'. Use HH:MM:SS for a clock time or +HH:MM:SS for an offset into the input`
+    );
+  }
+
+  static invalidRound(value: string): UpdateError {
+    return new UpdateError(`Invalid rounding unit '${value}'. Use a duration like 1s or 100ms`);
+  }
+
+  static optionRequires(option: string, required: string): UpdateError {
+    return new UpdateError(`${option} requires ${required}`);
+  }
+
+  static missingOption(option: string): UpdateError {
+    return new UpdateError(`Missing required option: ${option}`);
+  }
+
+  static conflictingOptions(first: string, second: string): UpdateError {
+    return new UpdateError(`${first} cannot be used together with ${second}`);
+  }
+
+  static invalidNumber(option: string, value: number): UpdateError {
+    return new UpdateError(`Invalid value '${value}' for ${option}. Use a whole number`);
+  }
+
+  static invalidFps(value: number): UpdateError {
+    return new UpdateError(`Invalid frame rate '${value}'. Use a positive number up to 100 (e.g. 25)`);
+  }
+
   static fileNotFound(path: string): UpdateError {
     return new UpdateError(`File not found: ${path}`);
   }
 
+  static nothingToContinue(path: string): UpdateError {
+    return new UpdateError(`Cannot continue from '${path}': it has no timestamped lines`);
+  }
+
+  static invalidFlatName(value: string): UpdateError {
+    return new UpdateError(`Invalid flat name '${value}'. Use 'start-end' or 'start'`);
+  }
+
+  static invalidName(value: string): UpdateError {
+    return new UpdateError(`Invalid name '${value}'. Use a plain filename without directories, e.g. meeting.txt`);
+  }
+
+  static invalidLayout(value: string): UpdateError {
+    return new UpdateError(`Invalid layout '${value}'. Use 'day' or 'iso-week'`);
+  }
+
+  static invalidMergeBase(value: string): UpdateError {
+    return new UpdateError(`Invalid merge base '${value}'. Use 'shared' or 'chained'`);
+  }
+
+  static invalidTies(value: string): UpdateError {
+    return new UpdateError(`Invalid ties policy '${value}'. Use 'allow', 'warn', or 'error'`);
+  }
+
+  static invalidFormat(value: string): UpdateError {
+    return new UpdateError(`Invalid output format '${value}'. Use 'text' or 'jsonl'`);
+  }
+
+  static invalidOutputEncoding(value: string): UpdateError {
+    return new UpdateError(`Invalid output encoding '${value}'. Use 'utf-8' or 'utf-16le'`);
+  }
+
+  static invalidSpeakerCase(value: string): UpdateError {
+    return new UpdateError(`Invalid speaker case '${value}'. Use 'title', 'upper', or 'lower'`);
+  }
+
+  static invalidVerifyDate(value: string): UpdateError {
+    return new UpdateError(`Invalid date verification policy '${value}'. Use 'warn' or 'error'`);
+  }
+
+  static dateMismatch(outputDate: string, suppliedDate: string): UpdateError {
+    return new UpdateError(
+      `The output would be dated ${outputDate}, not the supplied date ${suppliedDate} (rejected by --verify-date error)`
+    );
+  }
+
+  static tiedTimestamps(): UpdateError {
+    return new UpdateError('Consecutive timestamped lines share a timestamp (rejected by --ties error)');
+  }
+
+  static uninferableDate(path: string): UpdateError {
+    return new UpdateError(`Cannot infer a date from '${path}' for --auto-date; name it YYYYMMDD_HHMMSS_HHMMSS.txt`);
+  }
+
+  static uninferableStart(path: string): UpdateError {
+    return new UpdateError(
+      `Cannot infer the start time of '${path}'; name it YYYYMMDD_HHMMSS_HHMMSS.txt or update it on its own`
+    );
+  }
+
+  static invalidOwner(spec: string, reason: string): UpdateError {
+    return new UpdateError(`Invalid --chown '${spec}': ${reason}`);
+  }
+
+  static outputDirMissing(dir: string): UpdateError {
+    return new UpdateError(`Output directory does not exist: ${dir}`);
+  }
+
+  static outputNotWritable(dir: string, reason: string): UpdateError {
+    return new UpdateError(`Output directory '${dir}' is not writable: ${reason}`);
+  }
+
+  static specialOutput(option: string, path: string): UpdateError {
+    return new UpdateError(`${option} cannot be used with output '${path}', which is not a regular file`);
+  }
+
   static ioError(message: string): UpdateError {
     return new UpdateError(`I/O error: ${message}`);
   }
 }
 
+/**
+ * Errors related to the rename command.
+ */
+export class RenameError extends AppError {
+  constructor(message: string) {
+    super(message);
+    this.name = 'RenameError';
+  }
+
+  static missingDate(): RenameError {
+    return new RenameError('Missing required option: --date <date> (the date of the times in the files)');
+  }
+
+  static noMatches(pattern: string): RenameError {
+    return new RenameError(`No files matched pattern: ${pattern}`);
+  }
+
+  static targetExists(source: string, target: string): RenameError {
+    return new RenameError(`Cannot move ${source}: ${target} already exists`);
+  }
+
+  static ioError(message: string): RenameError {
+    return new RenameError(`I/O error: ${message}`);
+  }
+}
+
+/**
+ * Errors related to the diff command.
+ */
+export class DiffError extends AppError {
+  constructor(message: string) {
+    super(message);
+    this.name = 'DiffError';
+  }
+
+  static invalidTolerance(value: number): DiffError {
+    return new DiffError(`Invalid tolerance '${value}'. Must be a whole number of seconds, 0 or more`);
+  }
+
+  static ioError(message: string): DiffError {
+    return new DiffError(`I/O error: ${message}`);
+  }
+}
+
+/**
+ * Errors related to the cat command.
+ */
+export class CatError extends AppError {
+  constructor(message: string) {
+    super(message);
+    this.name = 'CatError';
+  }
+
+  static noMatches(pattern: string): CatError {
+    return new CatError(`No files matched pattern '${pattern}'`);
+  }
+
+  static fileTooLarge(path: string, size: number, max: number): CatError {
+    return new CatError(`File too large: ${path} (${size} bytes exceeds maximum of ${max} bytes)`);
+  }
+
+  static readFailed(path: string, error: Error): CatError {
+    return new CatError(`Failed to read ${path}: ${error.message}`);
+  }
+
+  static ioError(message: string): CatError {
+    return new CatError(`I/O error: ${message}`);
+  }
+}
+
 /**
  * Errors related to the merge command.
  */
@@ -73,6 +311,14 @@ export class MergeError extends AppError {
     return new MergeError(`No files matched pattern '${pattern}'`);
   }
 
+  static noSegments(pattern: string): MergeError {
+    return new MergeError(`No transcript segments (.txt or .txt.gz) among the files matched by pattern '${pattern}'`);
+  }
+
+  static allExcluded(pattern: string): MergeError {
+    return new MergeError(`Every file matched by pattern '${pattern}' was excluded by --exclude`);
+  }
+
   static unrecognizedFilename(filename: string): MergeError {
     return new MergeError(`Unrecognized transcript filename '${filename}'`);
   }
@@ -85,10 +331,98 @@ export class MergeError extends AppError {
     return new MergeError('Unable to determine an output filename; rerun with --output <file>');
   }
 
+  static invalidDateFormat(value: string): MergeError {
+    return new MergeError(
+      `Invalid date format '${value}'. Use date-fns tokens that produce a filesystem-safe name (e.g. yyyyMMdd)`
+    );
+  }
+
+  static invalidNumber(option: string, value: number): MergeError {
+    return new MergeError(`Invalid value '${value}' for ${option}. Use a whole number`);
+  }
+
+  static invalidOutputLocation(value: string): MergeError {
+    return new MergeError(`Invalid output location '${value}'. Use 'auto', 'nested', or 'cwd'`);
+  }
+
+  static invalidEol(value: string): MergeError {
+    return new MergeError(`Invalid line ending '${value}'. Use 'lf' or 'crlf'`);
+  }
+
+  static noHeaderDate(found: number): MergeError {
+    return new MergeError(`--date-header needs segments with exactly one date (found ${found})`);
+  }
+
+  static noNestedDirectory(found: number): MergeError {
+    return new MergeError(
+      `--output-location nested needs the nested segments in exactly one day directory (found ${found})`
+    );
+  }
+
+  static invalidUtf8(path: string): MergeError {
+    return new MergeError(`${path} is not valid UTF-8; convert it before merging`);
+  }
+
+  static tooFewSegments(found: number, minimum: number): MergeError {
+    return new MergeError(`Found ${found} segment(s) to merge but --min-segments requires at least ${minimum}`);
+  }
+
+  static nothingToDelete(): MergeError {
+    return new MergeError(
+      'No source files would be deleted (every match is the output itself); refusing with --expect-delete'
+    );
+  }
+
+  static newerOutput(path: string): MergeError {
+    return new MergeError(
+      `Output ${path} is newer than every source and may have been edited; drop --safe to overwrite it`
+    );
+  }
+
+  static invalidDuration(option: string, value: string): MergeError {
+    return new MergeError(`Invalid duration '${value}' for ${option}. Use a number with s, m, or h (e.g. 1h)`);
+  }
+
+  static missingOption(option: string): MergeError {
+    return new MergeError(`Missing required option: ${option}`);
+  }
+
+  static conflictingOptions(first: string, second: string): MergeError {
+    return new MergeError(`${first} cannot be used together with ${second}`);
+  }
+
+  static invalidSize(value: string): MergeError {
+    return new MergeError(`Invalid size '${value}'. Use a byte count with an optional K, M, or G suffix (e.g. 5M)`);
+  }
+
   static fileTooLarge(path: string, size: number, max: number): MergeError {
     return new MergeError(`File too large: ${path} (${size} bytes exceeds maximum of ${max} bytes)`);
   }
 
+  static outputIsDirectory(path: string): MergeError {
+    return new MergeError(`Output path '${path}' is an existing directory; name a file inside it instead`);
+  }
+
+  static specialOutput(option: string, path: string): MergeError {
+    return new MergeError(`${option} cannot be used with output '${path}', which is not a regular file`);
+  }
+
+  static verificationFailed(path: string, reason: string): MergeError {
+    return new MergeError(`Verification of '${path}' failed: ${reason}; no sources were deleted`);
+  }
+
+  static decompressedTooLarge(path: string, max: number): MergeError {
+    return new MergeError(`File too large: ${path} decompresses to more than the maximum of ${max} bytes`);
+  }
+
+  static invalidOwner(spec: string, reason: string): MergeError {
+    return new MergeError(`Invalid --chown '${spec}': ${reason}`);
+  }
+
+  static postMergeFailed(command: string, reason: string): MergeError {
+    return new MergeError(`Could not run post-merge command '${command}': ${reason}`);
+  }
+
   static ioError(message: string): MergeError {
     return new MergeError(`I/O error: ${message}`);
   }
@@ -2,9 +2,21 @@
  * Transcript processing logic for adjusting timestamps.
  */
 
-import { parse, format, add, differenceInSeconds } from 'date-fns';
-import { TIME_FORMAT, TIMESTAMP_REGEX } from './constants.js';
+import { format, addMilliseconds, addSeconds, differenceInMilliseconds } from 'date-fns';
+import {
+  TIME_FORMAT,
+  DATE_FORMAT_DASHED,
+  FRAME_TIMESTAMP_REGEX,
+  ANNOTATION_REGEX,
+  DATE_PREFIX_REGEX,
+  LEADING_WHITESPACE_REGEX,
+  LEADING_SPEAKER_REGEX,
+  ELAPSED_TIME_REGEX,
+  ELAPSED_TOKEN_REGEX,
+  MAX_LINE_BYTES,
+} from './constants.js';
 import { TranscriptError } from './errors.js';
+import { SpeakerCase } from './types.js';
 
 /**
  * Result of transcript processing.
@@ -18,33 +30,459 @@ export interface TranscriptUpdate {
   lastTimestamp: Date;
   /** True if timestamps were found out of chronological order. */
   hasOutOfOrderTimestamps: boolean;
+  /** True if a timestamp equals the one before it. */
+  hasTiedTimestamps: boolean;
+  /** Number of kept lines starting with an invalid timestamp-shaped token (e.g. `00:61:00`), copied as plain text. */
+  malformedLines: number;
+  /** Each adjusted leading timestamp and the output line it is on. */
+  timestamps: AdjustedTimestamp[];
 }
 
 /**
- * Parses a timestamp line and extracts the time and content.
+ * A timestamp as written to the adjusted output.
+ */
+export interface AdjustedTimestamp {
+  /** 1-based line number in the adjusted body. */
+  lineNumber: number;
+  /** The adjusted time. */
+  timestamp: Date;
+  /** Column of the output line where the text after the timestamp(s) begins. */
+  textStart: number;
+}
+
+/**
+ * A timestamp detected by {@link TranscriptProcessor.list}.
+ */
+export interface ListedTimestamp {
+  /** 1-based line number in the input. */
+  lineNumber: number;
+  /** The leading timestamp as it appeared, including a second one in dual mode. */
+  timestamp: string;
+  /** The text following the timestamp(s). */
+  text: string;
+}
+
+/**
+ * A timestamped line with its parsed time, as returned by {@link TranscriptProcessor.timeline}.
+ */
+export interface TimedLine extends ListedTimestamp {
+  /** The leading timestamp as an offset from midnight of the epoch day. */
+  time: Date;
+}
+
+/**
+ * What the parser extracted from a transcript, without adjusting anything.
+ */
+export interface TimestampListing {
+  /** Every timestamped line, in input order. */
+  timestamps: ListedTimestamp[];
+  /** Number of lines without a leading timestamp. */
+  plainLines: number;
+}
+
+/**
+ * Options controlling how timestamps are parsed and emitted.
+ */
+export interface AdjustOptions {
+  /**
+   * Frame rate for SMPTE-style HH:MM:SS:FF timestamps. When set, the fourth
+   * group is read as frames and re-emitted in the same form.
+   */
+  fps?: number;
+  /**
+   * Recognize a second leading timestamp (`start end text`) and offset both.
+   * The second timestamp of the final line becomes the transcript's last timestamp.
+   */
+  dualTimestamps?: boolean;
+  /**
+   * Collapse consecutive timestamped lines with the same speaker label into the
+   * first one, dropping the later timestamps. See {@link TranscriptProcessor.adjust}.
+   */
+  collapseSpeakers?: boolean;
+  /**
+   * Keep each line's original relative timestamp as ` (HH:MM:SS)` after the
+   * adjusted one. An existing annotation is replaced rather than stacked.
+   */
+  annotateOriginal?: boolean;
+  /** Minimum number of timestamped lines required (default 1). */
+  minTimestamps?: number;
+  /** Fixed duration in milliseconds (may be negative) added after rebasing. */
+  offset?: number;
+  /** Snap each adjusted timestamp to the nearest multiple of this many milliseconds (half rounds up). */
+  round?: number;
+  /** Milliseconds a timestamp may fall behind the previous one before it counts as out of order (default 0). */
+  outOfOrderTolerance?: number;
+  /** Rewrite the casing of the speaker label leading each timestamped line's text. */
+  speakerCase?: SpeakerCase;
+  /** Lines starting with this prefix are comments, passed through verbatim and never parsed. */
+  commentPrefix?: string;
+  /** Number of leading lines passed through verbatim without looking for timestamps. */
+  skipLines?: number;
+  /** Fail on any line longer than this many UTF-8 bytes (default {@link MAX_LINE_BYTES}). */
+  maxLineBytes?: number;
+  /** Keep only lines starting at or after this bound (inclusive). */
+  since?: TimeBound;
+  /** Keep only lines starting before this bound (exclusive). */
+  until?: TimeBound;
+  /**
+   * Called with each timestamp's 1-based input line, original text, and
+   * adjusted time as it is converted, for tracing the timestamp math. Timestamps
+   * dropped by the `since`/`until` window or folded by `collapseSpeakers` are not reported.
+   */
+  trace?: (lineNumber: number, original: string, adjusted: Date) => void;
+}
+
+/**
+ * One end of a `since`/`until` window.
+ */
+export interface TimeBound {
+  /** Milliseconds from midnight (absolute) or from 00:00:00 in the input's own timestamps (relative). */
+  milliseconds: number;
+  /** True to compare against the unadjusted input timestamp rather than the adjusted clock time. */
+  relative: boolean;
+}
+
+/**
+ * A timestamp token parsed from the start of some text.
+ */
+interface ParsedToken {
+  /** The relative time (date portion is the epoch day). */
+  time: Date;
+  /** Number of characters the token occupies. */
+  length: number;
+  /** The token as it appeared in the input. */
+  raw: string;
+  /** True if the timestamp carried a frame group (HH:MM:SS:FF). */
+  hasFrames: boolean;
+}
+
+/**
+ * A timestamped line split into its parts.
+ */
+interface ParsedLine {
+  /** Spaces and tabs before the timestamp, kept so indented lines stay indented. */
+  indent: string;
+  /** The `YYYY-MM-DD ` date prefix before the timestamp, or '' if there is none. */
+  datePrefix: string;
+  /** The leading timestamp. */
+  start: ParsedToken;
+  /** Optional second timestamp and the whitespace separating it from the first. */
+  end?: { token: ParsedToken; separator: string };
+  /** Everything following the timestamp token(s), starting with its delimiter (space or tab). */
+  rest: string;
+}
+
+/**
+ * Parses an elapsed HH:MM:SS time. Hours may exceed 23 so recordings longer
+ * than a day keep counting: `25:30:00` is one day and ninety minutes in.
+ * @param text The HH:MM:SS text
+ * @returns The time relative to midnight of the epoch day, or null if invalid
+ */
+function parseElapsed(text: string): Date | null {
+  const match = ELAPSED_TIME_REGEX.exec(text);
+  if (!match) {
+    return null;
+  }
+
+  const hours = parseInt(match[1], 10);
+  const minutes = parseInt(match[2], 10);
+  const seconds = parseInt(match[3], 10);
+  if (minutes > 59 || seconds > 59) {
+    return null;
+  }
+
+  const midnightRelative = new Date(0);
+  midnightRelative.setHours(0, 0, 0, 0);
+  return addSeconds(midnightRelative, hours * 3600 + minutes * 60 + seconds);
+}
+
+/**
+ * Parses a frame-based timestamp (HH:MM:SS:FF) at the start of some text.
+ * @param text The text to parse
+ * @param fps Frame rate used to convert frames to milliseconds
+ * @returns Parsed token, or null if the text does not start with a valid frame timestamp
+ */
+function parseFrameToken(text: string, fps: number): ParsedToken | null {
+  const match = FRAME_TIMESTAMP_REGEX.exec(text);
+  if (!match) {
+    return null;
+  }
+
+  const frames = parseInt(match[2], 10);
+  if (frames >= fps) {
+    return null;
+  }
+
+  const time = parseElapsed(match[1]);
+  if (!time) {
+    return null;
+  }
+
+  return {
+    time: addMilliseconds(time, Math.round((frames * 1000) / fps)),
+    length: match[0].length,
+    raw: match[0],
+    hasFrames: true,
+  };
+}
+
+/**
+ * Formats a timestamp as HH:MM:SS:FF for the given frame rate.
+ * Rounding that lands on a whole frame count rolls over into the next second.
+ * @param time The time to format
+ * @param fps The frame rate
+ * @returns The formatted timestamp
+ */
+function formatFrameTimestamp(time: Date, fps: number): string {
+  let whole = new Date(time);
+  whole.setMilliseconds(0);
+  let frames = Math.round((time.getMilliseconds() * fps) / 1000);
+
+  if (frames >= Math.ceil(fps)) {
+    whole = addSeconds(whole, 1);
+    frames = 0;
+  }
+
+  return `${format(whole, TIME_FORMAT)}:${String(frames).padStart(2, '0')}`;
+}
+
+/**
+ * Formats an adjusted timestamp in the same form it was parsed from.
+ * @param time The adjusted time
+ * @param token The token the time was parsed from
+ * @param options Parsing and formatting options
+ * @returns The formatted timestamp
+ */
+function formatTimestamp(time: Date, token: ParsedToken, options: AdjustOptions): string {
+  if (token.hasFrames && options.fps !== undefined) {
+    return formatFrameTimestamp(time, options.fps);
+  }
+  return format(time, TIME_FORMAT);
+}
+
+/**
+ * Parses a timestamp token at the start of some text.
+ * @param text The text to parse
+ * @param options Parsing options
+ * @returns Parsed token, or null if the text does not start with a timestamp
+ */
+function parseTimestampToken(text: string, options: AdjustOptions): ParsedToken | null {
+  // Frame-shaped tokens are handled exclusively when a frame rate is configured
+  if (options.fps !== undefined && FRAME_TIMESTAMP_REGEX.test(text)) {
+    return parseFrameToken(text, options.fps);
+  }
+
+  // Hours take as many digits as are given, so the token's length comes from the match
+  const raw = ELAPSED_TOKEN_REGEX.exec(text)?.[0];
+  if (raw === undefined) {
+    return null;
+  }
+  const time = parseElapsed(raw);
+  if (!time) {
+    return null;
+  }
+  return { time, length: raw.length, raw, hasFrames: false };
+}
+
+/**
+ * Parses a second timestamp following the leading one (dual-timestamp mode).
+ * The token must be preceded by whitespace and followed by whitespace or end of line.
+ * @param text The text following the leading timestamp
+ * @param options Parsing options
+ * @returns The second token and its separator, or null if absent
+ */
+function parseSecondTimestamp(
+  text: string,
+  options: AdjustOptions
+): { token: ParsedToken; separator: string } | null {
+  const separator = /^[ \t]+/.exec(text)?.[0];
+  if (!separator) {
+    return null;
+  }
+
+  const remainder = text.substring(separator.length);
+  const token = parseTimestampToken(remainder, options);
+  if (!token) {
+    return null;
+  }
+
+  const next = remainder.charAt(token.length);
+  if (next !== '' && !/\s/.test(next)) {
+    return null;
+  }
+
+  return { token, separator };
+}
+
+/**
+ * Parses a timestamp line and extracts the time(s) and content.
+ * Spaces and tabs before the timestamp are allowed and kept as the line's indent.
+ * A leading `YYYY-MM-DD ` date is recognized and ignored for the offset; it is
+ * re-emitted with the adjusted date.
  * @param line The line to parse
- * @returns Object with timestamp and content, or null if not a timestamp line
+ * @param options Parsing options
+ * @returns The parsed line, or null if not a timestamp line
  */
-function parseTimestampLine(line: string): { time: Date; rest: string } | null {
-  // Check minimum length for HH:MM:SS format
-  if (line.length < 8) {
+function parseTimestampLine(line: string, options: AdjustOptions): ParsedLine | null {
+  const indent = LEADING_WHITESPACE_REGEX.exec(line)?.[0] ?? '';
+  const body = line.substring(indent.length);
+  const datePrefix = DATE_PREFIX_REGEX.exec(body)?.[0] ?? '';
+
+  const start = parseTimestampToken(body.substring(datePrefix.length), options);
+  if (!start) {
     return null;
   }
 
-  // Extract first 8 characters
-  const timestampPart = line.substring(0, 8);
-  const rest = line.substring(8);
+  const rest = body.substring(datePrefix.length + start.length);
 
-  // Try to parse as time
-  try {
-    const time = parse(timestampPart, TIME_FORMAT, new Date(0));
-    if (isNaN(time.getTime())) {
-      return null;
+  if (options.dualTimestamps) {
+    const end = parseSecondTimestamp(rest, options);
+    if (end) {
+      return {
+        indent,
+        datePrefix,
+        start,
+        end,
+        rest: rest.substring(end.separator.length + end.token.length),
+      };
     }
-    return { time, rest };
-  } catch {
+  }
+
+  return { indent, datePrefix, start, rest };
+}
+
+/**
+ * Checks whether a line that did not parse as timestamped still starts (after
+ * any indent and date prefix) with a timestamp-shaped token, such as
+ * `00:61:00`, that is not a valid time.
+ */
+function startsWithInvalidTimestamp(line: string): boolean {
+  const body = line.substring(LEADING_WHITESPACE_REGEX.exec(line)?.[0].length ?? 0);
+  return ELAPSED_TOKEN_REGEX.test(body.substring(DATE_PREFIX_REGEX.exec(body)?.[0].length ?? 0));
+}
+
+/**
+ * Splits the text after a timestamp into a speaker label and inline text.
+ * `Speaker 1: hello` yields label `Speaker 1` and text `hello`; text without a
+ * colon (e.g. a Plaud `Speaker 1` header line) is entirely the label.
+ * @param rest The text following the timestamp
+ * @returns The label and text, or null if the line has no label
+ */
+function parseSpeakerLabel(rest: string): { label: string; text: string } | null {
+  const trimmed = rest.trim();
+  const colon = trimmed.indexOf(':');
+  const label = (colon > 0 ? trimmed.substring(0, colon) : trimmed).trim();
+  if (label.length === 0) {
     return null;
   }
+
+  const text = colon > 0 ? trimmed.substring(colon + 1).trim() : '';
+  return { label, text };
+}
+
+/**
+ * Recases the speaker label at the start of the text after a timestamp, leaving
+ * the rest of the line untouched. The label is the words before a `:` or a
+ * speaker number, so `SPEAKER 1: Hello` becomes `Speaker 1: Hello` in title
+ * case; text without such a label is returned as is.
+ * @param rest The text following the timestamp
+ * @param mode The casing to apply
+ */
+function normalizeSpeakerCase(rest: string, mode: SpeakerCase): string {
+  return rest.replace(LEADING_SPEAKER_REGEX, (_match, space: string, label: string) => {
+    if (mode === 'upper') {
+      return space + label.toUpperCase();
+    }
+    if (mode === 'lower') {
+      return space + label.toLowerCase();
+    }
+    const words = label.toLowerCase().split(' ');
+    return space + words.map((word) => word.charAt(0).toUpperCase() + word.substring(1)).join(' ');
+  });
+}
+
+/**
+ * Appends text to a line with a single space, keeping any trailing carriage return.
+ */
+function appendToLine(line: string, text: string): string {
+  const cr = line.endsWith('\r') ? '\r' : '';
+  return `${line.substring(0, line.length - cr.length)} ${text}${cr}`;
+}
+
+/**
+ * Rounds a time to the nearest multiple of a unit, with midpoints rounding up.
+ * @param time The time to round
+ * @param unit The unit in milliseconds
+ * @returns The rounded time
+ */
+function roundTo(time: Date, unit: number): Date {
+  return new Date(Math.floor((time.getTime() + unit / 2) / unit) * unit);
+}
+
+/**
+ * Yields the lines of a string split on '\n', like `split('\n')` but without
+ * materializing an array of every line.
+ * @throws TranscriptError if a line is longer than `maxLineBytes` in UTF-8
+ */
+function* iterateLines(contents: string, maxLineBytes = MAX_LINE_BYTES): Generator<string> {
+  let position = 0;
+  for (let lineNumber = 1; ; lineNumber++) {
+    const newline = contents.indexOf('\n', position);
+    const end = newline === -1 ? contents.length : newline;
+
+    // A UTF-16 unit is at most 3 UTF-8 bytes, so most lines skip the byte count
+    if ((end - position) * 3 > maxLineBytes) {
+      const bytes = Buffer.byteLength(contents.substring(position, end), 'utf-8');
+      if (bytes > maxLineBytes) {
+        throw TranscriptError.lineTooLong(lineNumber, bytes, maxLineBytes);
+      }
+    }
+
+    yield contents.substring(position, end);
+    if (newline === -1) {
+      return;
+    }
+    position = newline + 1;
+  }
+}
+
+/**
+ * Accumulates output lines into a single string instead of a line array that is
+ * joined (and copied) at the end. The most recent line stays open for amendment.
+ */
+class BodyBuilder {
+  private body = '';
+  private pending: string | null = null;
+  private count = 0;
+
+  /** Appends a line, committing the previous one. */
+  push(line: string): void {
+    if (this.pending !== null) {
+      this.body += this.pending + '\n';
+    }
+    this.pending = line;
+    this.count++;
+  }
+
+  /** Number of lines pushed so far. */
+  get length(): number {
+    return this.count;
+  }
+
+  /** The most recently pushed line. */
+  get last(): string {
+    return this.pending ?? '';
+  }
+
+  /** Replaces the most recently pushed line. */
+  replaceLast(line: string): void {
+    this.pending = line;
+  }
+
+  /** Returns the lines joined with '\n'. */
+  finish(): string {
+    return this.body + (this.pending ?? '');
+  }
 }
 
 /**
@@ -59,71 +497,333 @@ function applyOffset(startTime: Date, effectiveDate: Date, relativeTime: Date):
   const base = new Date(effectiveDate);
   base.setHours(startTime.getHours(), startTime.getMinutes(), startTime.getSeconds(), 0);
 
-  // Calculate offset in seconds from midnight
+  // Calculate offset from midnight (milliseconds keep frame precision)
   const midnightRelative = new Date(0);
   midnightRelative.setHours(0, 0, 0, 0);
-  const deltaSeconds = differenceInSeconds(relativeTime, midnightRelative);
+  const deltaMilliseconds = differenceInMilliseconds(relativeTime, midnightRelative);
 
   // Apply offset
-  return add(base, { seconds: deltaSeconds });
+  return addMilliseconds(base, deltaMilliseconds);
+}
+
+/**
+ * Yields each line with its 1-based number and parsed timestamp, if any.
+ * Skipped header lines and comments are yielded unparsed, and a trailing
+ * newline ends the last line rather than starting an empty one.
+ */
+function* numberedLines(
+  contents: string,
+  options: AdjustOptions
+): Generator<{ lineNumber: number; parsed: ParsedLine | null }> {
+  if (contents.length === 0) {
+    return;
+  }
+
+  const body = contents.endsWith('\n') ? contents.substring(0, contents.length - 1) : contents;
+  let lineNumber = 0;
+  for (const line of iterateLines(body, options.maxLineBytes)) {
+    lineNumber++;
+    const parsed =
+      lineNumber > (options.skipLines ?? 0) && !isComment(line, options) ? parseTimestampLine(line, options) : null;
+    yield { lineNumber, parsed };
+  }
+}
+
+/**
+ * Describes a parsed line for listings and reports.
+ */
+function toListed(lineNumber: number, parsed: ParsedLine): ListedTimestamp {
+  const timestamp = parsed.end ? parsed.start.raw + parsed.end.separator + parsed.end.token.raw : parsed.start.raw;
+  return { lineNumber, timestamp, text: parsed.rest.trim() };
+}
+
+/**
+ * Tests whether a line is a comment under the configured `commentPrefix`.
+ */
+function isComment(line: string, options: AdjustOptions): boolean {
+  // An empty prefix would match every line, so it disables comments instead
+  return !!options.commentPrefix && line.startsWith(options.commentPrefix);
+}
+
+/**
+ * Tests whether a line's timestamp falls inside the `since`/`until` window.
+ * Relative bounds compare the input timestamp; absolute bounds compare the
+ * adjusted time against that clock time on the effective date.
+ */
+function withinWindow(relativeTime: Date, adjusted: Date, effectiveDate: Date, options: AdjustOptions): boolean {
+  const position = (bound: TimeBound): number => {
+    if (bound.relative) {
+      const midnightRelative = new Date(0);
+      midnightRelative.setHours(0, 0, 0, 0);
+      return differenceInMilliseconds(relativeTime, midnightRelative);
+    }
+    const midnight = new Date(effectiveDate);
+    midnight.setHours(0, 0, 0, 0);
+    return differenceInMilliseconds(adjusted, midnight);
+  };
+
+  if (options.since && position(options.since) < options.since.milliseconds) {
+    return false;
+  }
+  if (options.until && position(options.until) >= options.until.milliseconds) {
+    return false;
+  }
+  return true;
 }
 
 /**
  * Processes a transcript by adjusting timestamps.
  */
 export class TranscriptProcessor {
+  /**
+   * Lists the timestamps the parser detects, for diagnosing format mismatches.
+   * Only the parsing options (`fps`, `dualTimestamps`, `skipLines`, `commentPrefix`)
+   * are consulted; skipped and comment lines count as plain.
+   * @param contents The transcript contents
+   * @param options Parsing options
+   * @returns The detected timestamps and the count of plain lines
+   */
+  static list(contents: string, options: AdjustOptions = {}): TimestampListing {
+    const timestamps: ListedTimestamp[] = [];
+    let plainLines = 0;
+
+    for (const { lineNumber, parsed } of numberedLines(contents, options)) {
+      if (parsed) {
+        timestamps.push(toListed(lineNumber, parsed));
+      } else {
+        plainLines++;
+      }
+    }
+
+    return { timestamps, plainLines };
+  }
+
+  /**
+   * Finds timestamped lines that are earlier than the timestamped line before
+   * them by more than `outOfOrderTolerance`. The input's own times are
+   * compared, so no start time is needed.
+   * @param contents The transcript contents
+   * @param options Parsing options, as for {@link TranscriptProcessor.list}, and the tolerance
+   * @returns The offending lines, empty if the timestamps are in order
+   */
+  static findOutOfOrder(contents: string, options: AdjustOptions = {}): ListedTimestamp[] {
+    const offending: ListedTimestamp[] = [];
+    const tolerance = options.outOfOrderTolerance ?? 0;
+    let previous: Date | null = null;
+
+    for (const { lineNumber, parsed } of numberedLines(contents, options)) {
+      if (!parsed) {
+        continue;
+      }
+      if (previous !== null && parsed.start.time.getTime() < previous.getTime() - tolerance) {
+        offending.push(toListed(lineNumber, parsed));
+      }
+      previous = parsed.start.time;
+    }
+
+    return offending;
+  }
+
+  /**
+   * Lists the timestamped lines with their parsed times, for comparing
+   * transcripts. Parsing options are consulted as for {@link TranscriptProcessor.list}.
+   * @param contents The transcript contents
+   * @param options Parsing options
+   * @returns Every timestamped line, in input order
+   */
+  static timeline(contents: string, options: AdjustOptions = {}): TimedLine[] {
+    const lines: TimedLine[] = [];
+    for (const { lineNumber, parsed } of numberedLines(contents, options)) {
+      if (parsed) {
+        lines.push({ ...toListed(lineNumber, parsed), time: parsed.start.time });
+      }
+    }
+    return lines;
+  }
+
+  /**
+   * Lists the distinct speaker labels of the timestamped lines in order of first
+   * appearance, ignoring the text after them. Labels are split off as
+   * `collapseSpeakers` does and compared exactly, so `Speaker 1` and `SPEAKER 1`
+   * are two labels. Parsing options are consulted as for {@link TranscriptProcessor.list}.
+   * @param contents The transcript contents
+   * @param options Parsing options
+   * @returns The labels, each once
+   */
+  static speakers(contents: string, options: AdjustOptions = {}): string[] {
+    const labels = new Set<string>();
+    for (const { parsed } of numberedLines(contents, options)) {
+      const speaker = parsed ? parseSpeakerLabel(parsed.rest) : null;
+      if (speaker) {
+        labels.add(speaker.label);
+      }
+    }
+    return [...labels];
+  }
+
+  /**
+   * Finds the first timestamped line, whose time and any `YYYY-MM-DD` date
+   * prefix serve as the base for `--auto-base`.
+   * @param contents The transcript contents
+   * @param options Parsing options, as for {@link TranscriptProcessor.list}
+   * @returns The first line's time and `YYYY-MM-DD` date (null without a prefix), or null if no line has a timestamp
+   */
+  static firstTimestamp(contents: string, options: AdjustOptions = {}): { time: Date; date: string | null } | null {
+    for (const { parsed } of numberedLines(contents, options)) {
+      if (parsed) {
+        return { time: parsed.start.time, date: parsed.datePrefix ? parsed.datePrefix.trim() : null };
+      }
+    }
+    return null;
+  }
+
   /**
    * Adjusts timestamps in a transcript.
+   *
+   * Only the timestamp anchored at the start of a line (after an optional date
+   * prefix) is adjusted, plus the one directly after it in `dualTimestamps`
+   * mode. Any other `HH:MM:SS` later in the line, such as `[00:00:01]` inside a
+   * speaker label, is left verbatim.
+   *
+   * With `collapseSpeakers`, a timestamped line whose speaker label matches the
+   * previous timestamped line is folded into it: its inline text is appended to
+   * the preceding non-blank line and its timestamp is dropped. Untimestamped
+   * continuation lines in between are kept in place and do not break the run.
+   *
+   * With `since`/`until`, timestamped lines outside the window are dropped
+   * together with the untimestamped continuation lines that follow them. Lines
+   * before the first timestamp are kept, and first/last timestamps, ordering,
+   * and the timestamp count only consider retained lines.
    * @param contents The transcript contents
    * @param baseTime The base start time (HH:MM:SS)
    * @param effectiveDate The effective date (YYYY-MM-DD)
+   * @param options Parsing and formatting options
    * @returns The processed transcript
    */
-  static adjust(contents: string, baseTime: Date, effectiveDate: Date): TranscriptUpdate {
-    const adjustedLines: string[] = [];
+  static adjust(
+    contents: string,
+    baseTime: Date,
+    effectiveDate: Date,
+    options: AdjustOptions = {}
+  ): TranscriptUpdate {
+    const output = new BodyBuilder();
+    const timestamps: AdjustedTimestamp[] = [];
     let firstTimestamp: Date | null = null;
     let lastTimestamp: Date | null = null;
     let previousTimestamp: Date | null = null;
     let hasOutOfOrder = false;
+    let hasTies = false;
+    let malformedLines = 0;
+    let currentSpeaker: string | null = null;
+    let timestampCount = 0;
+    let inWindow = true;
+    const toAbsolute = (relative: Date): Date => {
+      const absolute = addMilliseconds(applyOffset(baseTime, effectiveDate, relative), options.offset ?? 0);
+      return options.round ? roundTo(absolute, options.round) : absolute;
+    };
 
-    const lines = contents.split('\n');
+    const tolerance = options.outOfOrderTolerance ?? 0;
+    let skip = options.skipLines ?? 0;
+    let inputLine = 0;
 
-    for (const line of lines) {
-      const parsed = parseTimestampLine(line);
+    for (const line of iterateLines(contents, options.maxLineBytes)) {
+      inputLine++;
+      // Header lines are copied as-is, even if they contain timestamp-like tokens
+      if (skip > 0) {
+        skip--;
+        output.push(line);
+        continue;
+      }
+
+      const comment = isComment(line, options);
+      const parsed = comment ? null : parseTimestampLine(line, options);
 
       if (parsed) {
-        const adjusted = applyOffset(baseTime, effectiveDate, parsed.time);
+        const adjusted = toAbsolute(parsed.start.time);
+        inWindow = withinWindow(parsed.start.time, adjusted, effectiveDate, options);
+        if (!inWindow) {
+          continue;
+        }
+        timestampCount++;
 
         if (firstTimestamp === null) {
           firstTimestamp = adjusted;
         }
 
-        // Check for out-of-order timestamps
-        if (previousTimestamp !== null && adjusted < previousTimestamp) {
+        // Check for out-of-order timestamps beyond the tolerated jitter, tracking ties separately
+        if (previousTimestamp !== null && adjusted.getTime() < previousTimestamp.getTime() - tolerance) {
           hasOutOfOrder = true;
+        } else if (previousTimestamp !== null && adjusted.getTime() === previousTimestamp.getTime()) {
+          hasTies = true;
         }
         previousTimestamp = adjusted;
         lastTimestamp = adjusted;
 
-        // Format the adjusted timestamp and append the rest of the line
-        const formattedTime = format(adjusted, TIME_FORMAT);
-        adjustedLines.push(formattedTime + parsed.rest);
-      } else {
-        // Preserve non-timestamp lines as-is
-        adjustedLines.push(line);
+        // A previous annotation is dropped so re-annotating replaces it
+        let rest = options.annotateOriginal ? parsed.rest.replace(ANNOTATION_REGEX, '') : parsed.rest;
+        if (options.speakerCase) {
+          rest = normalizeSpeakerCase(rest, options.speakerCase);
+        }
+
+        if (options.collapseSpeakers) {
+          const speaker = parseSpeakerLabel(rest);
+          if (speaker && speaker.label === currentSpeaker) {
+            if (speaker.text.length > 0) {
+              if (output.last.trim().length > 0) {
+                output.replaceLast(appendToLine(output.last, speaker.text));
+              } else {
+                output.push(speaker.text);
+              }
+            }
+            continue;
+          }
+          currentSpeaker = speaker ? speaker.label : null;
+        }
+
+        // Only timestamps that reach the output are traced
+        options.trace?.(inputLine, parsed.start.raw, adjusted);
+
+        // Format the adjusted timestamp(s) and append the rest of the line
+        let formatted = formatTimestamp(adjusted, parsed.start, options);
+        if (parsed.datePrefix) {
+          formatted = `${format(adjusted, DATE_FORMAT_DASHED)} ${formatted}`;
+        }
+        if (parsed.end) {
+          const adjustedEnd = toAbsolute(parsed.end.token.time);
+          options.trace?.(inputLine, parsed.end.token.raw, adjustedEnd);
+          lastTimestamp = adjustedEnd;
+          formatted += parsed.end.separator + formatTimestamp(adjustedEnd, parsed.end.token, options);
+        }
+        if (options.annotateOriginal) {
+          formatted += ` (${parsed.start.raw})`;
+        }
+        const prefix = parsed.indent + formatted;
+        output.push(prefix + rest);
+        timestamps.push({ lineNumber: output.length, timestamp: adjusted, textStart: prefix.length });
+      } else if (inWindow) {
+        // Preserve non-timestamp lines as-is, counting those that look like a broken timestamp
+        if (!comment && startsWithInvalidTimestamp(line)) {
+          malformedLines++;
+        }
+        output.push(line);
       }
     }
 
     if (firstTimestamp === null) {
-      throw TranscriptError.noTimestamps();
+      throw options.since || options.until ? TranscriptError.emptyWindow() : TranscriptError.noTimestamps();
+    }
+
+    const minTimestamps = options.minTimestamps ?? 1;
+    if (timestampCount < minTimestamps) {
+      throw TranscriptError.tooFewTimestamps(timestampCount, minTimestamps);
     }
 
     if (lastTimestamp === null) {
       lastTimestamp = firstTimestamp;
     }
 
-    // Join lines and preserve trailing newline if present
-    let body = adjustedLines.join('\n');
+    // Preserve trailing newline if present
+    let body = output.finish();
     if (contents.endsWith('\n') && !body.endsWith('\n')) {
       body += '\n';
     }
@@ -133,6 +833,9 @@ export class TranscriptProcessor {
       firstTimestamp,
       lastTimestamp,
       hasOutOfOrderTimestamps: hasOutOfOrder,
+      hasTiedTimestamps: hasTies,
+      malformedLines,
+      timestamps,
     };
   }
 }
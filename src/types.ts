@@ -2,6 +2,22 @@
  * Type definitions for the plaud-timestamp CLI application.
  */
 
+/**
+ * Which times a flat output filename carries: `start-end` gives
+ * YYYYMMDD_HHMMSS_HHMMSS.txt and `start` gives YYYYMMDD_HHMMSS.txt.
+ */
+export type FlatName = 'start-end' | 'start';
+
+/**
+ * Directories of the nested layout: `day` for YYYY/MM/DD, `iso-week` for YYYY/Www.
+ */
+export type NestedLayout = 'day' | 'iso-week';
+
+/**
+ * Casing applied to speaker labels: `title` (Speaker 1), `upper` (SPEAKER 1), or `lower` (speaker 1).
+ */
+export type SpeakerCase = 'title' | 'upper' | 'lower';
+
 /**
  * Arguments for the update command.
  */
@@ -12,22 +28,264 @@ export interface UpdateArgs {
   outputDir?: string;
   /** When set, write output in flat mode (no subdirectories) to the current working directory. */
   flat: boolean;
-  /** Timestamp that will eventually adjust file entries (HH:MM:SS). */
-  time: string;
-  /** Calendar date associated with the update (YYYY-MM-DD). */
-  date: string;
+  /** Naming policy for flat output (default `start-end`). */
+  flatName?: FlatName;
+  /** Encoding of the output (default `utf-8`); `utf-16le` is written with a byte order mark. */
+  outputEncoding?: 'utf-8' | 'utf-16le';
+  /** Output format (default `text`); `jsonl` writes one JSON object per line to a `.jsonl` file. */
+  format?: 'text' | 'jsonl';
+  /** Fail if the output's date directory already contains `.txt` files. */
+  exclusiveDir?: boolean;
+  /** Set the output's modification time to the last adjusted timestamp instead of the write time. */
+  preserveMtime?: boolean;
+  /** Filename to use instead of the computed one; nested output keeps its date directories. */
+  name?: string;
+  /** Directories of the nested layout (default `day`). */
+  layout?: NestedLayout;
+  /**
+   * Start time the relative timestamps are rebased onto (HH:MM:SS), or
+   * `YYYY-MM-DD HH:MM:SS` to also set the date; required unless `offset` is set.
+   */
+  time?: string;
+  /** Calendar date associated with the update (YYYY-MM-DD); required unless `time` carries one. */
+  date?: string;
+  /** Extra accepted layout for `date`: MM/DD/YYYY, DD/MM/YYYY, DD.MM.YYYY, or YYYY-MM-DD. */
+  dateInputFormat?: string;
+  /** Frame rate for HH:MM:SS:FF timestamps; frames are ignored when unset. */
+  fps?: number;
+  /** Adjust a second leading timestamp (start and end) on each line. */
+  dualTimestamps?: boolean;
+  /** Fold consecutive lines from the same speaker into the first one. */
+  collapseSpeakers?: boolean;
+  /** Print a unified diff of the changes instead of writing the output. */
+  diff?: boolean;
+  /** Keep the original relative timestamp as an annotation after the adjusted one. */
+  annotateOriginal?: boolean;
+  /** Fail when fewer than this many lines carry timestamps (default 1). */
+  minTimestamps?: number;
+  /** Treat lines starting with this prefix (e.g. `//`) as comments that are never adjusted. */
+  commentPrefix?: string;
+  /** Further inputs adjusted after `file` and concatenated into the same output. */
+  mergeFiles?: string[];
+  /**
+   * How merged inputs are based: `shared` (default) rebases every input onto the
+   * same start, for timestamps that continue across files; `chained` restarts
+   * each input at zero from the previous input's last timestamp.
+   */
+  mergeBase?: 'shared' | 'chained';
+  /**
+   * What to do when consecutive timestamped lines share a timestamp: `allow`
+   * (default), `warn`, or `error`, for tools that need strictly increasing times.
+   */
+  ties?: 'allow' | 'warn' | 'error';
+  /** Jitter (HH:MM:SS) a timestamp may fall behind the previous one before it counts as out of order (default 0). */
+  oooTolerance?: string;
+  /**
+   * Check that the output's date (that of the last timestamp) is the supplied date, and
+   * `warn` or fail with `error` when midnight overflow or an offset moved it; unchecked by default.
+   */
+  verifyDate?: 'warn' | 'error';
+  /** Recase the speaker label at the start of each timestamped line's text. */
+  normalizeSpeakerCase?: SpeakerCase;
+  /** Copy this many leading lines through unchanged before looking for timestamps. */
+  skipLines?: number;
+  /** Fail on any input line longer than this many bytes (default 1 MB). */
+  maxLineBytes?: number;
+  /** Optional file listing each output path with its first/last timestamps. */
+  index?: string;
+  /** Remove blank lines from the start and end of the adjusted output. */
+  trim?: boolean;
+  /** Also print this many leading lines of the adjusted output after writing it. */
+  preview?: number;
+  /** Write an empty output for a zero-byte input instead of failing; its span is just the start time. */
+  allowEmpty?: boolean;
+  /** Also write `HH:MM:SS<tab>line` for each adjusted timestamp to the output path with a `.idx` extension. */
+  timestampIndex?: boolean;
+  /** Also write a `<output>.sha256` sidecar with the output's SHA-256 for `sha256sum -c`. */
+  checksum?: boolean;
+  /** Shift already-absolute timestamps by this [+|-]HH:MM:SS duration instead of rebasing; conflicts with `time`. */
+  offset?: string;
+  /**
+   * Rebase onto the last timestamp of this already-updated transcript, so the input continues
+   * where it ends; its date comes from its first line's `YYYY-MM-DD` prefix, else `date`.
+   */
+  continueFrom?: string;
+  /** Print each timestamp's original value and adjusted date-time to stderr as it is converted. */
+  trace?: boolean;
+  /**
+   * Take the base from the input's first timestamp, keeping every time as it is (plus any `offset`);
+   * the date comes from that line's `YYYY-MM-DD` prefix, else `date`. Conflicts with `time`.
+   */
+  autoBase?: boolean;
+  /**
+   * Take the date from the input's flat-format name (YYYYMMDD_HHMMSS_HHMMSS.txt) instead of `date`;
+   * a `date` given as well is ignored with a warning.
+   */
+  autoDate?: boolean;
+  /** Directory for temporary files during atomic writes (default: the output's directory). */
+  tempDir?: string;
+  /** Retry transient write failures this many times with backoff (default 0). */
+  retries?: number;
+  /** Fail instead of creating the output's parent directory when it is missing. */
+  noCreateDirs?: boolean;
+  /** Skip flushing written files to disk before they are renamed into place, for speed. */
+  noFsync?: boolean;
+  /** Flush the output directory after each rename, so the rename survives a crash (POSIX only). */
+  fsyncDir?: boolean;
+  /** `user:group` (names or ids) to own written files and created directories; ignored on Windows. */
+  chown?: string;
+  /** Also append warnings to this file; they are printed either way. */
+  logFile?: string;
+  /** Snap adjusted timestamps to the nearest unit, e.g. `1s` or `100ms` (requires `fps`). */
+  round?: string;
+  /** Only list the detected timestamps; nothing is adjusted or written (CLI only). */
+  listOnly?: boolean;
+  /** Print only the number of timestamped lines, for scripts; writes nothing. */
+  countOnly?: boolean;
+  /** Print only the distinct speaker labels in order of first appearance; writes nothing (CLI only). */
+  speakersOnly?: boolean;
+  /** Drop lines before this bound: `HH:MM:SS` (adjusted clock time) or `+HH:MM:SS` (input time); inclusive. */
+  since?: string;
+  /** Drop lines from this bound on: `HH:MM:SS` (adjusted clock time) or `+HH:MM:SS` (input time); exclusive. */
+  until?: string;
+}
+
+/**
+ * Arguments for the rename command.
+ */
+export interface RenameArgs {
+  /** Files or glob patterns to rename into the canonical layout. */
+  patterns: string[];
+  /** Calendar date of the clock times in the files (YYYY-MM-DD). */
+  date?: string;
+  /** Extra accepted layout for `date`, as for update. */
+  dateInputFormat?: string;
+  /** Prefix directory for the nested layout. */
+  outputDir?: string;
+  /** Rename to the flat layout in the current directory instead of the nested one. */
+  flat: boolean;
+  /** Report the moves without performing them. */
+  dryRun?: boolean;
+}
+
+/**
+ * Arguments for the diff command.
+ */
+export interface DiffArgs {
+  /** The first transcript. */
+  a: string;
+  /** The second transcript. */
+  b: string;
+  /** Largest difference in seconds at which lines with the same text are paired (default 2). */
+  tolerance?: number;
+  /** Print a JSON report instead of the human-readable one. */
+  json?: boolean;
+}
+
+/**
+ * Arguments for the cat command.
+ */
+export interface CatArgs {
+  /** Files or glob patterns to concatenate, in the order given. */
+  patterns: string[];
+  /** Output file; without one the result goes to stdout. */
+  output?: string;
+  /** Directory for the temporary file instead of the output's parent. */
+  tempDir?: string;
+  /** Fail instead of creating a missing output directory. */
+  noCreateDirs?: boolean;
+  /** Skip flushing written files to disk before they are renamed into place, for speed. */
+  noFsync?: boolean;
+  /** Flush the output directory after each rename, so the rename survives a crash (POSIX only). */
+  fsyncDir?: boolean;
+  /** Times to retry a write step that fails with a transient error (default 0). */
+  retries?: number;
 }
 
 /**
  * Arguments for the merge command.
  */
 export interface MergeArgs {
-  /** One or more files or glob patterns to merge, e.g. 2025/01/27/*. */
+  /** One or more files, directories, or glob patterns to merge, e.g. 2025/01/27/*. */
   patterns: string[];
   /** Optional explicit output file to override the inferred location. */
   output?: string;
+  /**
+   * Where an inferred output goes: `auto` (default) uses the shared nested day
+   * directory if every file is in one, else the first file's directory; `nested`
+   * always uses the nested segments' day directory; `cwd` the current directory
+   * (or `baseDir` when given).
+   */
+  outputLocation?: 'auto' | 'nested' | 'cwd';
+  /** Write one output per day to `<root>/YYYY/MM/DD/` instead of next to the segments. */
+  outputRoot?: string;
+  /** Directory that relative patterns and a relative `output` are resolved against instead of the cwd. */
+  baseDir?: string;
   /** Preserve the original segments instead of deleting them after merging. */
   noDelete: boolean;
+  /** date-fns format for the date portion of the inferred output filename (default yyyy-MM-dd). */
+  dateFormat?: string;
+  /**
+   * date-fns format of single-directory day names (e.g. `yyyy_MM_dd`), tried before the
+   * built-in `YYYY/MM/DD` and `YYYY-MM-DD` shapes when inferring a nested segment's date.
+   */
+  dayDirFormat?: string;
+  /** Print only the number of segments the patterns match, for scripts; merges nothing. */
+  countOnly?: boolean;
+  /** Stream segments into the output one at a time instead of joining them in memory first. */
+  stream?: boolean;
+  /** Rewrite every line ending to `lf` or `crlf`; by default each segment keeps its own. */
+  eol?: 'lf' | 'crlf';
+  /** Start the output with a `# YYYY-MM-DD` line for the segments' date and a blank line. */
+  dateHeader?: boolean;
+  /** Gzip the output, adding `.gz` to its name if missing; an `output` ending in `.gz` implies this. */
+  compress?: boolean;
+  /** Offset each segment's `Speaker N` numbers past the previous segments' so they don't collide. */
+  renumberSpeakers?: boolean;
+  /** Fail if fewer than this many segments are left to merge once the output itself is excluded (default 1). */
+  minSegments?: number;
+  /** Fail if no source would be deleted, e.g. because every match was the output itself. */
+  expectDelete?: boolean;
+  /** Merge in the order the files were given instead of sorting by date and start time. */
+  preserveOrder?: boolean;
+  /** Refuse to overwrite an existing output that is newer than every source. */
+  safe?: boolean;
+  /** Compute a SHA-256 of the merged output and the total source size. */
+  checksum?: boolean;
+  /** Only keep glob matches at most this many directories below the pattern's literal prefix. */
+  maxDepth?: number;
+  /** Let wildcards and directory arguments match dotfiles such as .061901-111901.txt. */
+  hidden?: boolean;
+  /** Glob patterns whose matches are dropped from the segments; bare names match in each segment's directory. */
+  exclude?: string[];
+  /** Split the output into numbered parts no larger than this size (e.g. 5M), at segment boundaries. */
+  maxOutputSize?: string;
+  /** Print a JSON summary instead of the human-readable output (CLI only). */
+  json?: boolean;
+  /** HH:MM:SS start for an inferred `<date>_HHMMSS-HHMMSS.txt` name, instead of the first filename's. */
+  start?: string;
+  /** HH:MM:SS end to name the inferred output with instead of the last filename's. */
+  end?: string;
+  /** Write one output per run of segments whose gaps are at most this duration (e.g. `1h`). */
+  groupByGap?: string;
+  /** Read each written output back and check it matches the merged content before deleting any source. */
+  verify?: boolean;
+  /** Shell command to run after a successful merge; `{output}` becomes the quoted output path(s). */
+  postMergeCmd?: string;
+  /** Directory for temporary files during atomic writes (default: the output's directory). */
+  tempDir?: string;
+  /** Retry transient write failures this many times with backoff (default 0). */
+  retries?: number;
+  /** Fail instead of creating the output's parent directory when it is missing. */
+  noCreateDirs?: boolean;
+  /** Skip flushing written files to disk before they are renamed into place, for speed. */
+  noFsync?: boolean;
+  /** Flush the output directory after each rename, so the rename survives a crash (POSIX only). */
+  fsyncDir?: boolean;
+  /** `user:group` (names or ids) to own written files and created directories; ignored on Windows. */
+  chown?: string;
+  /** Also append warnings to this file; they are printed either way. */
+  logFile?: string;
 }
 
 /**
@@ -0,0 +1,273 @@
+/**
+ * Line-based unified diff generation.
+ */
+
+/**
+ * A single line in an edit script.
+ */
+interface DiffOp {
+  /** ' ' for unchanged, '-' for removed, '+' for added. */
+  kind: ' ' | '-' | '+';
+  /** The line content without its newline. */
+  line: string;
+}
+
+/**
+ * Splits text into lines, ignoring the empty element after a trailing newline.
+ */
+function splitLines(text: string): string[] {
+  if (text.length === 0) {
+    return [];
+  }
+  const lines = text.split('\n');
+  if (text.endsWith('\n')) {
+    lines.pop();
+  }
+  return lines;
+}
+
+/**
+ * Aligns two equal-length line arrays index by index. Linear time, which keeps
+ * diffs of large transcripts cheap when adjustment only rewrote lines in place.
+ */
+function alignPairwise(a: string[], b: string[]): DiffOp[] {
+  const ops: DiffOp[] = [];
+  let i = 0;
+
+  while (i < a.length) {
+    if (a[i] === b[i]) {
+      ops.push({ kind: ' ', line: a[i] });
+      i++;
+      continue;
+    }
+
+    // Group a run of changed lines as removals followed by additions
+    const start = i;
+    while (i < a.length && a[i] !== b[i]) {
+      i++;
+    }
+    for (let j = start; j < i; j++) {
+      ops.push({ kind: '-', line: a[j] });
+    }
+    for (let j = start; j < i; j++) {
+      ops.push({ kind: '+', line: b[j] });
+    }
+  }
+
+  return ops;
+}
+
+/**
+ * Finds where a shortest edit script between two ranges crosses the middle,
+ * running Myers' search forwards from the start and backwards from the end
+ * until the paths overlap. Only the two frontiers are kept, so memory stays
+ * linear in the input however many edits there are.
+ * @returns The split point in both arrays, or null when the ranges share nothing
+ */
+function findMiddle(
+  a: string[],
+  aStart: number,
+  aEnd: number,
+  b: string[],
+  bStart: number,
+  bEnd: number
+): [number, number] | null {
+  const n = aEnd - aStart;
+  const m = bEnd - bStart;
+  const maxD = Math.ceil((n + m) / 2);
+  const offset = maxD;
+  const size = 2 * maxD + 2;
+  const forward = new Array<number>(size).fill(-1);
+  const backward = new Array<number>(size).fill(-1);
+  forward[offset + 1] = 0;
+  backward[offset + 1] = 0;
+  const delta = n - m;
+  // With an odd delta the forward path reaches the overlap first
+  const checkForward = delta % 2 !== 0;
+
+  // Diagonals trimmed from each end once a path runs off the edit graph
+  let forwardStartTrim = 0;
+  let forwardEndTrim = 0;
+  let backwardStartTrim = 0;
+  let backwardEndTrim = 0;
+
+  for (let d = 0; d < maxD; d++) {
+    for (let k = -d + forwardStartTrim; k <= d - forwardEndTrim; k += 2) {
+      const index = offset + k;
+      let x =
+        k === -d || (k !== d && forward[index - 1] < forward[index + 1])
+          ? forward[index + 1]
+          : forward[index - 1] + 1;
+      let y = x - k;
+      while (x < n && y < m && a[aStart + x] === b[bStart + y]) {
+        x++;
+        y++;
+      }
+      forward[index] = x;
+      if (x > n) {
+        forwardEndTrim += 2;
+      } else if (y > m) {
+        forwardStartTrim += 2;
+      } else if (checkForward) {
+        const mirror = offset + delta - k;
+        if (mirror >= 0 && mirror < size && backward[mirror] !== -1 && x >= n - backward[mirror]) {
+          return [aStart + x, bStart + y];
+        }
+      }
+    }
+
+    for (let k = -d + backwardStartTrim; k <= d - backwardEndTrim; k += 2) {
+      const index = offset + k;
+      let x =
+        k === -d || (k !== d && backward[index - 1] < backward[index + 1])
+          ? backward[index + 1]
+          : backward[index - 1] + 1;
+      let y = x - k;
+      while (x < n && y < m && a[aEnd - x - 1] === b[bEnd - y - 1]) {
+        x++;
+        y++;
+      }
+      backward[index] = x;
+      if (x > n) {
+        backwardEndTrim += 2;
+      } else if (y > m) {
+        backwardStartTrim += 2;
+      } else if (!checkForward) {
+        const mirror = offset + delta - k;
+        if (mirror >= 0 && mirror < size && forward[mirror] !== -1) {
+          const forwardX = forward[mirror];
+          if (forwardX >= n - x) {
+            return [aStart + forwardX, bStart + forwardX - (mirror - offset)];
+          }
+        }
+      }
+    }
+  }
+
+  return null;
+}
+
+/**
+ * Appends a shortest edit script between two ranges to `ops` with Myers'
+ * linear-space algorithm: common ends are matched directly, and the rest is
+ * split where the edit script crosses its middle and solved in halves.
+ */
+function alignRange(
+  a: string[],
+  aStart: number,
+  aEnd: number,
+  b: string[],
+  bStart: number,
+  bEnd: number,
+  ops: DiffOp[]
+): void {
+  while (aStart < aEnd && bStart < bEnd && a[aStart] === b[bStart]) {
+    ops.push({ kind: ' ', line: a[aStart] });
+    aStart++;
+    bStart++;
+  }
+  let common = 0;
+  while (aEnd - common > aStart && bEnd - common > bStart && a[aEnd - common - 1] === b[bEnd - common - 1]) {
+    common++;
+  }
+  aEnd -= common;
+  bEnd -= common;
+
+  const middle = aStart < aEnd && bStart < bEnd ? findMiddle(a, aStart, aEnd, b, bStart, bEnd) : null;
+  if (middle !== null) {
+    alignRange(a, aStart, middle[0], b, bStart, middle[1], ops);
+    alignRange(a, middle[0], aEnd, b, middle[1], bEnd, ops);
+  } else {
+    // One side is empty, or the two share no line at all
+    for (let i = aStart; i < aEnd; i++) {
+      ops.push({ kind: '-', line: a[i] });
+    }
+    for (let j = bStart; j < bEnd; j++) {
+      ops.push({ kind: '+', line: b[j] });
+    }
+  }
+
+  for (let i = aEnd; i < aEnd + common; i++) {
+    ops.push({ kind: ' ', line: a[i] });
+  }
+}
+
+/**
+ * Computes a shortest edit script with Myers' algorithm in linear space.
+ * Used when line counts differ and lines cannot be paired directly.
+ */
+function alignMyers(a: string[], b: string[]): DiffOp[] {
+  const ops: DiffOp[] = [];
+  alignRange(a, 0, a.length, b, 0, b.length, ops);
+  return ops;
+}
+
+/**
+ * Produces a unified diff between two texts.
+ * @param oldText The original text
+ * @param newText The updated text
+ * @param oldLabel Label for the original side (--- line)
+ * @param newLabel Label for the updated side (+++ line)
+ * @param context Number of unchanged context lines around each change
+ * @returns The unified diff, or an empty string if the texts are identical
+ */
+export function unifiedDiff(
+  oldText: string,
+  newText: string,
+  oldLabel: string,
+  newLabel: string,
+  context = 3
+): string {
+  const a = splitLines(oldText);
+  const b = splitLines(newText);
+  const ops = a.length === b.length ? alignPairwise(a, b) : alignMyers(a, b);
+
+  const changes: number[] = [];
+  ops.forEach((op, index) => {
+    if (op.kind !== ' ') {
+      changes.push(index);
+    }
+  });
+  if (changes.length === 0) {
+    return '';
+  }
+
+  // Line numbers (1-based) at the start of each op
+  const oldLineAt: number[] = [];
+  const newLineAt: number[] = [];
+  let oldLine = 1;
+  let newLine = 1;
+  for (const op of ops) {
+    oldLineAt.push(oldLine);
+    newLineAt.push(newLine);
+    if (op.kind !== '+') oldLine++;
+    if (op.kind !== '-') newLine++;
+  }
+
+  const output: string[] = [`--- ${oldLabel}`, `+++ ${newLabel}`];
+  let c = 0;
+  while (c < changes.length) {
+    // Extend the hunk while the next change is within two context windows
+    const start = Math.max(0, changes[c] - context);
+    let last = changes[c];
+    while (c + 1 < changes.length && changes[c + 1] - last <= context * 2) {
+      c++;
+      last = changes[c];
+    }
+    const end = Math.min(ops.length, last + context + 1);
+
+    const hunk = ops.slice(start, end);
+    const oldCount = hunk.filter((op) => op.kind !== '+').length;
+    const newCount = hunk.filter((op) => op.kind !== '-').length;
+    const oldStart = oldCount === 0 ? oldLineAt[start] - 1 : oldLineAt[start];
+    const newStart = newCount === 0 ? newLineAt[start] - 1 : newLineAt[start];
+
+    output.push(`@@ -${oldStart},${oldCount} +${newStart},${newCount} @@`);
+    for (const op of hunk) {
+      output.push(op.kind + op.line);
+    }
+    c++;
+  }
+
+  return output.join('\n') + '\n';
+}
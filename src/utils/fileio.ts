@@ -2,31 +2,256 @@
  * File I/O utilities for atomic write operations.
  */
 
-import { promises as fs } from 'fs';
+import { promises as fs, createWriteStream, Stats } from 'fs';
 import { dirname, join } from 'path';
-import { tmpdir } from 'os';
 import { randomBytes } from 'crypto';
+import { gzipSync, createGzip } from 'zlib';
+import { pipeline } from 'stream/promises';
+import { Owner } from './ownership.js';
+import { SizeError } from '../errors.js';
+
+/**
+ * Options for atomic writes.
+ */
+export interface AtomicWriteOptions {
+  /**
+   * Directory for the temporary file instead of the output's parent. When it is
+   * on a different filesystem, the rename falls back to a copy, which is not atomic.
+   */
+  tempDir?: string;
+  /** Times to retry a write step that fails with a transient error (default 0). */
+  retries?: number;
+  /** Gzip-compress the content before writing it. */
+  gzip?: boolean;
+  /** Owner for the output and any directories created for it (POSIX only). */
+  owner?: Owner;
+  /** Create missing parent directories (default true); when false, a missing parent is an error. */
+  createDirs?: boolean;
+  /** Text encoding of the written file (default `utf-8`); `utf-16le` starts with a byte order mark. */
+  encoding?: 'utf-8' | 'utf-16le';
+  /** Flush the temporary file to disk before the rename (default true); off trades durability for speed. */
+  fsync?: boolean;
+  /** Also flush the output's directory after the rename, so the rename itself survives a crash (POSIX only). */
+  fsyncDir?: boolean;
+}
+
+/** Error codes worth retrying: the kind network filesystems return under load. */
+const TRANSIENT_ERROR_CODES = new Set(['EAGAIN', 'EBUSY', 'EIO', 'ETIMEDOUT', 'ECONNRESET', 'ESTALE']);
+
+/** Delay before the first retry; doubled for each further attempt. */
+const RETRY_BASE_DELAY_MS = 100;
+
+/**
+ * Runs an I/O operation, retrying transient failures with exponential backoff.
+ * Other errors (missing paths, permissions, bad input) are rethrown at once.
+ * @param operation The operation to run
+ * @param retries How many times to retry after the first failure
+ * @param baseDelayMs Delay before the first retry
+ * @returns The operation's result
+ */
+export async function withRetries<T>(
+  operation: () => Promise<T>,
+  retries: number,
+  baseDelayMs = RETRY_BASE_DELAY_MS
+): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await operation();
+    } catch (error: any) {
+      if (attempt >= retries || !TRANSIENT_ERROR_CODES.has(error?.code)) {
+        throw error;
+      }
+      await new Promise((resolve) => setTimeout(resolve, baseDelayMs * 2 ** attempt));
+    }
+  }
+}
+
+/**
+ * Copies the permission bits of an existing output onto the temp file so that
+ * overwriting keeps them instead of taking the umask default. POSIX only: on
+ * Windows the mode carries no permission bits worth preserving.
+ */
+async function copyExistingMode(outputPath: string, tempPath: string): Promise<void> {
+  if (process.platform === 'win32') {
+    return;
+  }
+
+  let mode: number;
+  try {
+    mode = (await fs.stat(outputPath)).mode;
+  } catch {
+    // Nothing to preserve for a new file
+    return;
+  }
+  await fs.chmod(tempPath, mode & 0o7777);
+}
+
+/**
+ * Hands a path to the requested owner. A no-op on Windows, which has no
+ * numeric owners.
+ */
+async function applyOwner(path: string, owner: Owner | undefined): Promise<void> {
+  if (owner && process.platform !== 'win32') {
+    await fs.chown(path, owner.uid, owner.gid);
+  }
+}
+
+/**
+ * Flushes a file or directory to disk. Files are opened for writing, which
+ * Windows needs to flush them; a directory is opened read-only, which is all
+ * POSIX needs.
+ */
+async function syncToDisk(path: string, flags: 'r' | 'r+'): Promise<void> {
+  const handle = await fs.open(path, flags);
+  try {
+    await handle.sync();
+  } finally {
+    await handle.close();
+  }
+}
+
+/**
+ * Checks whether a path is an existing special file (a FIFO, device, or
+ * socket) that must be written in place rather than replaced by a rename.
+ * Symlinks are followed, so `/dev/stdout` counts. Missing paths do not.
+ */
+export async function isSpecialFile(path: string): Promise<boolean> {
+  try {
+    const stats = await fs.stat(path);
+    return !stats.isFile() && !stats.isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Encodes content for writing, compressing it when requested: the exact bytes
+ * {@link atomicWrite} writes for string content.
+ */
+export function encodeContent(content: string, options: AtomicWriteOptions): Buffer {
+  const encoded =
+    options.encoding === 'utf-16le' ? Buffer.from('\uFEFF' + content, 'utf16le') : Buffer.from(content, 'utf-8');
+  return options.gzip ? gzipSync(encoded) : encoded;
+}
+
+/**
+ * Writes streamed content to a file chunk by chunk, encoding and compressing
+ * it on the way, so the whole content is never held in memory. Buffer chunks
+ * are written as they are.
+ */
+async function writeChunks(
+  path: string,
+  chunks: AsyncIterable<string | Buffer>,
+  options: AtomicWriteOptions
+): Promise<void> {
+  const utf16 = options.encoding === 'utf-16le';
+  async function* encode(): AsyncGenerator<Buffer> {
+    if (utf16) {
+      yield Buffer.from('\uFEFF', 'utf16le');
+    }
+    for await (const chunk of chunks) {
+      yield Buffer.isBuffer(chunk) ? chunk : Buffer.from(chunk, utf16 ? 'utf16le' : 'utf-8');
+    }
+  }
+
+  if (options.gzip) {
+    await pipeline(encode(), createGzip(), createWriteStream(path));
+  } else {
+    await pipeline(encode(), createWriteStream(path));
+  }
+}
+
+/**
+ * Moves a file into place, copying when the rename crosses filesystems.
+ * An existing file at the destination is replaced.
+ * @param tempPath The file to move
+ * @param outputPath Where to move it
+ */
+export async function moveIntoPlace(tempPath: string, outputPath: string): Promise<void> {
+  try {
+    await fs.rename(tempPath, outputPath);
+  } catch (error: any) {
+    if (error.code !== 'EXDEV') {
+      throw error;
+    }
+    await fs.copyFile(tempPath, outputPath);
+    await fs.unlink(tempPath);
+  }
+}
 
 /**
  * Writes content to a file atomically using a temporary file and rename.
+ * Overwriting an existing file keeps its permission bits. With an owner, the
+ * temp file is chowned before the rename so the output never appears with the
+ * wrong owner.
+ *
+ * A special file such as a named pipe or `/dev/stdout` cannot be replaced by
+ * a rename, so it is written directly instead; that write is not atomic, and
+ * the owner and permissions of the special file are left alone.
+ *
+ * With `createDirs: false`, a missing parent directory fails the write
+ * instead of being created.
+ *
+ * The temporary file is flushed to disk before the rename unless `fsync` is
+ * false, so a crash cannot leave the output name on incomplete content. With
+ * `fsyncDir`, the directory is flushed after the rename as well.
+ *
+ * Content given as a Buffer is taken to be encoded already (see
+ * {@link encodeContent}) and is written as is. Content given as chunks is
+ * streamed into the temporary file as it is produced. A stream can only be consumed once, so writing it is not retried;
+ * creating directories and the rename still are.
  * @param outputPath The final output path
- * @param content The content to write
+ * @param content The content to write: text, encoded bytes, or a stream of chunks
+ * @param options Where to place the temporary file, retries, compression, and ownership
  */
-export async function atomicWrite(outputPath: string, content: string): Promise<void> {
-  // Create parent directories if they don't exist
+export async function atomicWrite(
+  outputPath: string,
+  content: string | Buffer | AsyncIterable<string | Buffer>,
+  options: AtomicWriteOptions = {}
+): Promise<void> {
+  const retries = options.retries ?? 0;
+  const data =
+    typeof content === 'string' ? encodeContent(content, options) : Buffer.isBuffer(content) ? content : null;
+  const write = (path: string): Promise<void> =>
+    data !== null ? fs.writeFile(path, data) : writeChunks(path, content as AsyncIterable<string | Buffer>, options);
+
+  // Stream straight into pipes and devices; there is nothing to rename over
+  if (await isSpecialFile(outputPath)) {
+    await write(outputPath);
+    return;
+  }
+
   const dir = dirname(outputPath);
-  await fs.mkdir(dir, { recursive: true });
+  if (options.createDirs === false && !(await isDirectory(dir))) {
+    throw new Error(`Output directory does not exist: ${dir}`);
+  }
+
+  // Create parent directories if they don't exist, giving new ones the owner too
+  const created = await withRetries(() => fs.mkdir(dir, { recursive: true }), retries);
+  if (created !== undefined) {
+    for (let path = dir; ; path = dirname(path)) {
+      await applyOwner(path, options.owner);
+      if (path === created || dirname(path) === path) {
+        break;
+      }
+    }
+  }
 
-  // Create a temporary file in the same directory for atomic rename
+  // Create a temporary file next to the output (or in tempDir) for the rename
   const tempFileName = `.tmp-${randomBytes(8).toString('hex')}`;
-  const tempPath = join(dir, tempFileName);
+  const tempPath = join(options.tempDir ?? dir, tempFileName);
 
   try {
     // Write to temp file
-    await fs.writeFile(tempPath, content, 'utf-8');
+    await (data !== null ? withRetries(() => write(tempPath), retries) : write(tempPath));
+    if (options.fsync !== false) {
+      await withRetries(() => syncToDisk(tempPath, 'r+'), retries);
+    }
+    await copyExistingMode(outputPath, tempPath);
+    await applyOwner(tempPath, options.owner);
 
     // Atomically rename to final destination
-    await fs.rename(tempPath, outputPath);
+    await withRetries(() => moveIntoPlace(tempPath, outputPath), retries);
   } catch (error) {
     // Clean up temp file if it exists
     try {
@@ -36,23 +261,108 @@ export async function atomicWrite(outputPath: string, content: string): Promise<
     }
     throw error;
   }
+
+  // Directories cannot be opened for syncing on Windows
+  if (options.fsyncDir && process.platform !== 'win32') {
+    await withRetries(() => syncToDisk(dir, 'r'), retries);
+  }
+}
+
+/**
+ * Ensures a directory exists and that files can be created in it.
+ * @param dir The directory to probe
+ * @param createDirs Create the directory if missing (default true); when false, a missing directory is an error
+ * @throws The underlying error if the directory cannot be created or written
+ */
+export async function probeWritable(dir: string, createDirs = true): Promise<void> {
+  if (createDirs) {
+    await fs.mkdir(dir, { recursive: true });
+  } else if (!(await isDirectory(dir))) {
+    throw new Error(`Output directory does not exist: ${dir}`);
+  }
+  const probePath = join(dir, `.probe-${randomBytes(8).toString('hex')}`);
+  await fs.writeFile(probePath, '');
+  await fs.unlink(probePath);
 }
 
 /**
- * Reads a file and validates its size.
+ * Decodes file content as text: UTF-16 (little- or big-endian) when it starts
+ * with the matching byte order mark, as some Windows tools export, else UTF-8.
+ * A UTF-16 byte order mark is dropped; a UTF-8 one is kept.
+ * @param bytes The raw file content
+ * @returns The decoded text
+ */
+export function decodeText(bytes: Buffer): string {
+  if (bytes[0] === 0xff && bytes[1] === 0xfe) {
+    return new TextDecoder('utf-16le').decode(bytes);
+  }
+  if (bytes[0] === 0xfe && bytes[1] === 0xff) {
+    return new TextDecoder('utf-16be').decode(bytes);
+  }
+  return bytes.toString('utf-8');
+}
+
+/**
+ * Checks that a file is no larger than a limit before it is read. A file of
+ * exactly `maxSize` bytes passes.
+ * @param filePath The file to check
+ * @param maxSize Maximum allowed file size in bytes
+ * @returns The file's metadata, so callers need not stat it again
+ * @throws SizeError if the file is too large; the underlying error if it cannot be stat'd
+ */
+export async function checkSize(filePath: string, maxSize: number): Promise<Stats> {
+  const stats = await fs.stat(filePath);
+  if (stats.size > maxSize) {
+    throw new SizeError(filePath, stats.size, maxSize);
+  }
+  return stats;
+}
+
+/**
+ * Reads a file and validates its size. UTF-16 files with a byte order mark
+ * are decoded (see {@link decodeText}).
  * @param filePath The file to read
  * @param maxSize Maximum allowed file size in bytes
  * @returns The file contents
- * @throws Error if file is too large
+ * @throws SizeError if file is too large
  */
 export async function readFileWithSizeLimit(filePath: string, maxSize: number): Promise<string> {
-  const stats = await fs.stat(filePath);
+  await checkSize(filePath, maxSize);
+  return decodeText(await fs.readFile(filePath));
+}
 
-  if (stats.size > maxSize) {
-    throw new Error(`File too large: ${stats.size} bytes exceeds maximum of ${maxSize} bytes`);
+/**
+ * Checks whether a file's content begins with the given prefix.
+ * @param filePath The file to inspect
+ * @param prefix The expected leading text
+ * @returns true if the file starts with the prefix, false otherwise (including unreadable files)
+ */
+export async function fileStartsWith(filePath: string, prefix: string): Promise<boolean> {
+  let handle: fs.FileHandle | undefined;
+  try {
+    handle = await fs.open(filePath, 'r');
+    const expected = Buffer.from(prefix, 'utf-8');
+    const buffer = Buffer.alloc(expected.length);
+    const { bytesRead } = await handle.read(buffer, 0, expected.length, 0);
+    return bytesRead === expected.length && buffer.equals(expected);
+  } catch {
+    return false;
+  } finally {
+    await handle?.close();
   }
+}
 
-  return await fs.readFile(filePath, 'utf-8');
+/**
+ * Checks whether a path exists and is a directory.
+ * @param path The path to check
+ * @returns true if the path is a directory, false otherwise (including missing paths)
+ */
+export async function isDirectory(path: string): Promise<boolean> {
+  try {
+    return (await fs.stat(path)).isDirectory();
+  } catch {
+    return false;
+  }
 }
 
 /**
@@ -0,0 +1,80 @@
+/**
+ * Resolution of `user:group` ownership specs for written files.
+ */
+
+import { promises as fs } from 'fs';
+
+/**
+ * Numeric owner to apply with chown; -1 leaves that part unchanged.
+ */
+export interface Owner {
+  uid: number;
+  gid: number;
+}
+
+/**
+ * Account databases consulted for names. Overridable for tests.
+ */
+export interface AccountDatabases {
+  passwd: string;
+  group: string;
+}
+
+const SYSTEM_DATABASES: AccountDatabases = { passwd: '/etc/passwd', group: '/etc/group' };
+
+/**
+ * Looks up the numeric id of a name in a colon-separated account database
+ * (`name:password:id:...`, as in /etc/passwd and /etc/group).
+ * @returns The id, or null if the name is not listed
+ */
+async function lookupId(database: string, name: string): Promise<number | null> {
+  const contents = await fs.readFile(database, 'utf-8');
+  for (const line of contents.split('\n')) {
+    const fields = line.split(':');
+    if (fields[0] === name && fields.length >= 3 && /^\d+$/.test(fields[2])) {
+      return Number(fields[2]);
+    }
+  }
+  return null;
+}
+
+/**
+ * Resolves one side of an ownership spec: a numeric id is used as is, a name
+ * is looked up, and an empty part means "unchanged".
+ */
+async function resolvePart(part: string, database: string, kind: 'user' | 'group'): Promise<number> {
+  if (part === '') {
+    return -1;
+  }
+  if (/^\d+$/.test(part)) {
+    return Number(part);
+  }
+
+  const id = await lookupId(database, part);
+  if (id === null) {
+    throw new Error(`Unknown ${kind} '${part}'`);
+  }
+  return id;
+}
+
+/**
+ * Resolves a `user:group`, `user`, or `:group` spec to numeric ids. Names are
+ * looked up in the local account databases, so directory-service accounts
+ * missing from them must be given by number.
+ * @param spec The ownership spec
+ * @param databases Account databases to read names from
+ * @returns The ids to pass to chown
+ * @throws Error if the spec is malformed or a name is unknown
+ */
+export async function resolveOwner(spec: string, databases: AccountDatabases = SYSTEM_DATABASES): Promise<Owner> {
+  const parts = spec.split(':');
+  if (parts.length > 2 || spec === '' || spec === ':') {
+    throw new Error('Use user:group, user, or :group');
+  }
+
+  const [user, group = ''] = parts;
+  return {
+    uid: await resolvePart(user, databases.passwd, 'user'),
+    gid: await resolvePart(group, databases.group, 'group'),
+  };
+}
@@ -0,0 +1,19 @@
+/**
+ * Path display helpers for command output.
+ */
+
+import { sep } from 'path';
+
+/**
+ * Formats a path for display, optionally using forward slashes on every platform.
+ * @param path The path to display
+ * @param forwardSlashes When true, replace the platform separator with '/'
+ * @param separator The platform separator (overridable for tests)
+ * @returns The display form of the path
+ */
+export function displayPath(path: string, forwardSlashes: boolean, separator: string = sep): string {
+  if (!forwardSlashes || separator === '/') {
+    return path;
+  }
+  return path.split(separator).join('/');
+}
@@ -2,9 +2,10 @@
  * Input validation utilities.
  */
 
-import { parse, isValid } from 'date-fns';
-import { TIME_FORMAT, DATE_FORMAT_DASHED } from '../constants.js';
-import { UpdateError } from '../errors.js';
+import { parse, format, isValid } from 'date-fns';
+import { TIME_FORMAT, DATE_FORMAT_DASHED, DATETIME_FORMAT, DATE_INPUT_FORMATS } from '../constants.js';
+import { UpdateError, MergeError } from '../errors.js';
+import type { TimeBound } from '../transcript.js';
 
 /**
  * Validates and parses a time string in HH:MM:SS format.
@@ -21,17 +22,214 @@ export function validateTime(timeStr: string): Date {
 }
 
 /**
- * Validates and parses a date string in YYYY-MM-DD format.
+ * Validates a --time value, which is either HH:MM:SS or a combined
+ * `YYYY-MM-DD HH:MM:SS` (or ISO `YYYY-MM-DDTHH:MM:SS`) that also sets the date.
+ * @param value The time argument to validate
+ * @returns The parsed time, plus the date when one was embedded
+ * @throws UpdateError if invalid
+ */
+export function validateTimeArgument(value: string): { time: Date; date?: Date } {
+  if (!/^\d{4}-\d{2}-\d{2}[ T]/.test(value)) {
+    return { time: validateTime(value) };
+  }
+
+  const combined = parse(value.replace('T', ' '), DATETIME_FORMAT, new Date());
+  if (!isValid(combined)) {
+    throw UpdateError.invalidTime(value);
+  }
+
+  const date = new Date(combined.getFullYear(), combined.getMonth(), combined.getDate());
+  const time = new Date(0);
+  time.setHours(combined.getHours(), combined.getMinutes(), combined.getSeconds(), 0);
+  return { time, date };
+}
+
+/**
+ * Validates and parses a date string. YYYY-MM-DD is always accepted, and so is
+ * DD.MM.YYYY, whose dots are unambiguous. Slashed dates read as either MM/DD or
+ * DD/MM, so they need `inputFormat` to say which.
  * @param dateStr The date string to validate
+ * @param inputFormat An additional accepted format, one of {@link DATE_INPUT_FORMATS}
  * @returns Parsed Date object
+ * @throws UpdateError if invalid, or slashed without an input format
+ */
+export function validateDate(dateStr: string, inputFormat?: string): Date {
+  const formats = [DATE_FORMAT_DASHED, DATE_INPUT_FORMATS['DD.MM.YYYY']];
+  if (inputFormat !== undefined) {
+    if (!Object.hasOwn(DATE_INPUT_FORMATS, inputFormat)) {
+      throw UpdateError.invalidDateInputFormat(inputFormat);
+    }
+    formats.push(DATE_INPUT_FORMATS[inputFormat]);
+  }
+
+  for (const pattern of formats) {
+    const date = parse(dateStr, pattern, new Date());
+    if (isValid(date)) {
+      return date;
+    }
+  }
+
+  if (inputFormat === undefined && /^\d{1,2}\/\d{1,2}\/\d{4}$/.test(dateStr)) {
+    throw UpdateError.ambiguousDate(dateStr);
+  }
+  throw UpdateError.invalidDate(dateStr);
+}
+
+/**
+ * Validates and parses a signed duration in [+|-]HH:MM:SS format.
+ * @param value The duration string to validate
+ * @returns The duration in milliseconds (negative for a leading '-')
+ * @throws UpdateError if invalid
+ */
+export function validateOffset(value: string): number {
+  const match = /^([+-])?(\d{2}):(\d{2}):(\d{2})$/.exec(value);
+  if (!match) {
+    throw UpdateError.invalidOffset(value);
+  }
+
+  const [, sign, hours, minutes, seconds] = match;
+  if (parseInt(minutes, 10) > 59 || parseInt(seconds, 10) > 59) {
+    throw UpdateError.invalidOffset(value);
+  }
+
+  const total = (parseInt(hours, 10) * 3600 + parseInt(minutes, 10) * 60 + parseInt(seconds, 10)) * 1000;
+  return sign === '-' ? -total : total;
+}
+
+/**
+ * Validates and parses the `--ooo-tolerance` duration (HH:MM:SS, not negative).
+ * @param value The tolerance to validate
+ * @returns The tolerance in milliseconds
+ * @throws UpdateError if invalid
+ */
+export function validateTolerance(value: string): number {
+  const match = /^(\d{2}):(\d{2}):(\d{2})$/.exec(value);
+  if (!match || parseInt(match[2], 10) > 59 || parseInt(match[3], 10) > 59) {
+    throw UpdateError.invalidTolerance(value);
+  }
+
+  const [, hours, minutes, seconds] = match;
+  return (parseInt(hours, 10) * 3600 + parseInt(minutes, 10) * 60 + parseInt(seconds, 10)) * 1000;
+}
+
+/**
+ * Validates and parses a --since/--until bound: `HH:MM:SS` for an adjusted
+ * clock time, or `+HH:MM:SS` for a position in the input's own timestamps.
+ * @param option The option name, for error messages
+ * @param value The bound to validate
+ * @returns The parsed bound
+ * @throws UpdateError if invalid
+ */
+export function validateBound(option: string, value: string): TimeBound {
+  const match = /^(\+)?(\d{2}):(\d{2}):(\d{2})$/.exec(value);
+  if (!match) {
+    throw UpdateError.invalidBound(option, value);
+  }
+
+  const [, plus, hours, minutes, seconds] = match;
+  if (parseInt(minutes, 10) > 59 || parseInt(seconds, 10) > 59) {
+    throw UpdateError.invalidBound(option, value);
+  }
+
+  const milliseconds = (parseInt(hours, 10) * 3600 + parseInt(minutes, 10) * 60 + parseInt(seconds, 10)) * 1000;
+  return { milliseconds, relative: plus === '+' };
+}
+
+/**
+ * Validates and parses a rounding unit such as `1s` or `100ms`.
+ * @param value The unit to validate
+ * @returns The unit in milliseconds
  * @throws UpdateError if invalid
  */
-export function validateDate(dateStr: string): Date {
-  const date = parse(dateStr, DATE_FORMAT_DASHED, new Date());
-  if (!isValid(date)) {
-    throw UpdateError.invalidDate(dateStr);
+export function validateRound(value: string): number {
+  const match = /^(\d+)(ms|s)$/.exec(value);
+  const amount = match ? parseInt(match[1], 10) : 0;
+  if (!match || amount === 0) {
+    throw UpdateError.invalidRound(value);
+  }
+  return match[2] === 's' ? amount * 1000 : amount;
+}
+
+/**
+ * Validates a frame rate for HH:MM:SS:FF timestamps.
+ * @param fps The frame rate to validate
+ * @returns The frame rate
+ * @throws UpdateError if not a positive number that fits a two-digit frame field
+ */
+export function validateFps(fps: number): number {
+  if (!Number.isFinite(fps) || fps <= 0 || fps > 100) {
+    throw UpdateError.invalidFps(fps);
+  }
+  return fps;
+}
+
+/**
+ * Validates a whole-number option value.
+ * @param option The option name for error messages (e.g. --min-timestamps)
+ * @param value The value to validate
+ * @param min The smallest accepted value
+ * @returns The value
+ * @throws UpdateError if not an integer of at least `min`
+ */
+export function validateCount(option: string, value: number, min = 0): number {
+  if (!Number.isInteger(value) || value < min) {
+    throw UpdateError.invalidNumber(option, value);
+  }
+  return value;
+}
+
+/**
+ * Validates and parses a size such as `512K`, `5M`, or `1G` (binary units).
+ * @param value The size to validate
+ * @returns The size in bytes
+ * @throws MergeError if invalid
+ */
+export function validateSize(value: string): number {
+  const match = /^(\d+)([KMG])?B?$/i.exec(value.trim());
+  if (!match || parseInt(match[1], 10) === 0) {
+    throw MergeError.invalidSize(value);
+  }
+
+  const multipliers: Record<string, number> = { K: 1024, M: 1024 ** 2, G: 1024 ** 3 };
+  const unit = match[2]?.toUpperCase();
+  return parseInt(match[1], 10) * (unit ? multipliers[unit] : 1);
+}
+
+/**
+ * Validates and parses a duration such as `90s`, `30m`, or `1h`.
+ * @param option The option name for error messages (e.g. --group-by-gap)
+ * @param value The duration to validate
+ * @returns The duration in milliseconds
+ * @throws MergeError if invalid
+ */
+export function validateDuration(option: string, value: string): number {
+  const match = /^(\d+)([smh])$/.exec(value.trim());
+  if (!match || parseInt(match[1], 10) === 0) {
+    throw MergeError.invalidDuration(option, value);
+  }
+
+  const multipliers: Record<string, number> = { s: 1000, m: 60 * 1000, h: 60 * 60 * 1000 };
+  return parseInt(match[1], 10) * multipliers[match[2]];
+}
+
+/**
+ * Validates a date-fns format string used to name merged output files.
+ * @param pattern The format string to validate
+ * @returns The format string
+ * @throws MergeError if the pattern is invalid or yields an unsafe filename
+ */
+export function validateDateFormat(pattern: string): string {
+  let sample: string;
+  try {
+    sample = format(new Date(2000, 0, 1), pattern);
+  } catch {
+    throw MergeError.invalidDateFormat(pattern);
+  }
+
+  if (sample.trim().length === 0 || /[/\\:*?"<>|]/.test(sample) || /^\.+$/.test(sample)) {
+    throw MergeError.invalidDateFormat(pattern);
   }
-  return date;
+  return pattern;
 }
 
 /**
@@ -0,0 +1,103 @@
+/**
+ * Structured warnings returned by commands for library consumers.
+ */
+
+import { promises as fs } from 'fs';
+
+/**
+ * Machine-readable warning kinds.
+ */
+export type WarningKind =
+  | 'outOfOrderTimestamps'
+  | 'tiedTimestamps'
+  | 'newerOutput'
+  | 'zeroLengthSpan'
+  | 'dateMismatch'
+  | 'dateOverridden'
+  | 'malformedLines'
+  | 'emptyInput';
+
+/**
+ * A non-fatal condition encountered while running a command.
+ */
+export class Warning {
+  constructor(
+    /** Machine-readable kind for programmatic handling. */
+    readonly kind: WarningKind,
+    /** Human-readable description. */
+    readonly message: string
+  ) {}
+
+  static outOfOrderTimestamps(): Warning {
+    return new Warning('outOfOrderTimestamps', 'timestamps in input were not in chronological order');
+  }
+
+  static tiedTimestamps(): Warning {
+    return new Warning('tiedTimestamps', 'consecutive timestamped lines share a timestamp');
+  }
+
+  static zeroLengthSpan(): Warning {
+    return new Warning(
+      'zeroLengthSpan',
+      'the first and last timestamps are the same, so the output name has no duration; check the input is complete'
+    );
+  }
+
+  static dateMismatch(outputDate: string, suppliedDate: string): Warning {
+    return new Warning('dateMismatch', `the output is dated ${outputDate}, not the supplied date ${suppliedDate}`);
+  }
+
+  static dateOverridden(inferredDate: string, suppliedDate: string): Warning {
+    return new Warning('dateOverridden', `used ${inferredDate} from the file name instead of --date ${suppliedDate}`);
+  }
+
+  static malformedLines(count: number): Warning {
+    return new Warning(
+      'malformedLines',
+      `${count} line(s) start with an invalid timestamp, such as a minute over 59, and were kept unchanged`
+    );
+  }
+
+  static emptyInput(path: string): Warning {
+    return new Warning('emptyInput', `${path} is empty and added nothing to the output`);
+  }
+
+  static newerOutput(path: string): Warning {
+    return new Warning('newerOutput', `overwrote ${path}, which was newer than every source`);
+  }
+}
+
+/** Exit code for a run that produced warnings under `--fail-on-warning`. */
+export const WARNING_EXIT_CODE = 1;
+
+/**
+ * Chooses the exit code warnings call for: with `--fail-on-warning`, any
+ * warning fails the run even though its output was written.
+ * @param warnings Every warning the run produced
+ * @param failOnWarning Whether warnings should fail the run
+ * @returns WARNING_EXIT_CODE, or 0 when the run may succeed
+ */
+export function warningExitCode(warnings: Warning[], failOnWarning: boolean): number {
+  return failOnWarning && warnings.length > 0 ? WARNING_EXIT_CODE : 0;
+}
+
+/**
+ * Appends warnings to a log file, one tab-separated line each:
+ * `<ISO time>\t<file>\t<kind>\t<message>`. The file is created if missing.
+ * @param logFile The log to append to
+ * @param file The file the warnings concern
+ * @param warnings The warnings to record; nothing is written when empty
+ * @param at When the warnings occurred
+ */
+export async function appendWarningLog(
+  logFile: string,
+  file: string,
+  warnings: Warning[],
+  at: Date = new Date()
+): Promise<void> {
+  if (warnings.length === 0) {
+    return;
+  }
+  const lines = warnings.map((warning) => `${at.toISOString()}\t${file}\t${warning.kind}\t${warning.message}\n`);
+  await fs.appendFile(logFile, lines.join(''), 'utf-8');
+}
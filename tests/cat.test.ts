@@ -0,0 +1,73 @@
+/**
+ * Tests for the cat command.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { PassThrough } from 'stream';
+import { gzipSync } from 'zlib';
+import { executeCat } from '../src/commands/cat.js';
+
+describe('executeCat', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-cat-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  async function write(name: string, contents: string): Promise<string> {
+    const path = join(root, name);
+    await fs.writeFile(path, contents, 'utf-8');
+    return path;
+  }
+
+  it('should concatenate arbitrarily named files in argument order', async () => {
+    const zeta = await write('zeta notes.md', '18:00:05 Speaker 2: Second\n');
+    const alpha = await write('alpha', 'no timestamps here');
+    const misc = await write('misc.txt', '09:00:00 Speaker 1: Earlier but last\n');
+    const output = join(root, 'out', 'combined.txt');
+
+    const outcome = await executeCat({ patterns: [zeta, alpha, misc], output });
+
+    expect(outcome.files).toEqual([zeta, alpha, misc]);
+    expect(await fs.readFile(output, 'utf-8')).toBe(
+      '18:00:05 Speaker 2: Second\nno timestamps here\n09:00:00 Speaker 1: Earlier but last\n'
+    );
+    // Sources are never deleted
+    expect(await fs.readFile(alpha, 'utf-8')).toBe('no timestamps here');
+  });
+
+  it('should write to the sink without an output and reject an unmatched pattern', async () => {
+    await write('b.log', 'two\n');
+    await write('a.log', 'one\n');
+    const sink = new PassThrough();
+    const chunks: string[] = [];
+    sink.on('data', (chunk) => chunks.push(chunk.toString()));
+
+    await executeCat({ patterns: [join(root, '*.log')] }, sink);
+
+    expect(chunks.join('')).toBe('one\ntwo\n');
+    await expect(executeCat({ patterns: [join(root, '*.nothing')] }, sink)).rejects.toThrow('No files matched');
+  });
+
+  it('should copy bytes verbatim without decompressing, decoding, or stripping', async () => {
+    const gzipped = gzipSync(Buffer.from('compressed\n'));
+    const latin1 = Buffer.from([0xef, 0xbb, 0xbf, 0x63, 0x61, 0x66, 0xe9, 0x0a]);
+    const first = join(root, 'a.txt.gz');
+    const second = join(root, 'b.bin');
+    await fs.writeFile(first, gzipped);
+    await fs.writeFile(second, latin1);
+    const output = join(root, 'raw.out');
+
+    await executeCat({ patterns: [first, second], output });
+
+    // The gzip stream does not end in a newline, so one separates the files
+    expect(await fs.readFile(output)).toEqual(Buffer.concat([gzipped, Buffer.from('\n'), latin1]));
+  });
+});
@@ -0,0 +1,80 @@
+/**
+ * Tests for the diff command.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { executeDiff, formatDiffReport } from '../src/commands/diff.js';
+import { unifiedDiff } from '../src/utils/diff.js';
+
+describe('executeDiff', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-diff-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  async function write(name: string, contents: string): Promise<string> {
+    const path = join(root, name);
+    await fs.writeFile(path, contents, 'utf-8');
+    return path;
+  }
+
+  it('should report an altered line and a shifted timestamp', async () => {
+    const a = await write(
+      'a.txt',
+      '18:00:01 Speaker 1: Hello\nplain text\n18:00:05 Speaker 2: See you at noon\n18:00:09 Speaker 1: Bye\n'
+    );
+    const b = await write(
+      'b.txt',
+      '18:00:01 Speaker 1: Hello\nplain text\n18:00:05 Speaker 2: See you at one\n18:00:10 Speaker 1: Bye\n'
+    );
+
+    const result = await executeDiff({ a, b });
+
+    expect(result.identical).toBe(1);
+    expect(result.onlyInA.map((line) => line.lineNumber)).toEqual([3]);
+    expect(result.onlyInB.map((line) => line.lineNumber)).toEqual([3]);
+    expect(formatDiffReport(result)).toEqual([
+      '- 18:00:05 Speaker 2: See you at noon (a:3)',
+      '+ 18:00:05 Speaker 2: See you at one (b:3)',
+      '~ 18:00:09 -> 18:00:10 Speaker 1: Bye (a:4, b:4)',
+    ]);
+  });
+
+  it('should not pair lines further apart than the tolerance', async () => {
+    const a = await write('a.txt', '18:00:01 Speaker 1: Hello\n');
+    const b = await write('b.txt', '18:00:04 Speaker 1: Hello\n');
+
+    expect((await executeDiff({ a, b, tolerance: 2 })).shifted).toEqual([]);
+    expect((await executeDiff({ a, b, tolerance: 3 })).shifted).toHaveLength(1);
+    await expect(executeDiff({ a, b, tolerance: -1 })).rejects.toThrow('Invalid tolerance');
+  });
+});
+
+describe('unifiedDiff', () => {
+  it('should find a shortest edit script when line counts differ', () => {
+    const oldText = 'a\nb\nc\nd\ne\nf\n';
+    const newText = 'a\nc\nd\nx\ne\nf\ng\n';
+
+    expect(unifiedDiff(oldText, newText, 'old', 'new', 0)).toBe(
+      '--- old\n+++ new\n@@ -2,1 +1,0 @@\n-b\n@@ -4,0 +4,1 @@\n+x\n@@ -6,0 +7,1 @@\n+g\n'
+    );
+  });
+
+  it('should diff long inputs with many edits', () => {
+    const lines = Array.from({ length: 4000 }, (_, i) => `line ${i}`);
+    const kept = lines.filter((_, i) => i % 2 === 0);
+
+    const diff = unifiedDiff(lines.join('\n') + '\n', kept.join('\n') + '\n', 'old', 'new', 0);
+
+    expect(diff.split('\n').filter((line) => line.startsWith('-line'))).toHaveLength(2000);
+    expect(diff).not.toContain('+line');
+  });
+});
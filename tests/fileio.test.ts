@@ -0,0 +1,156 @@
+/**
+ * Tests for file I/O utilities.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { execFileSync } from 'child_process';
+import { atomicWrite, checkSize, withRetries } from '../src/utils/fileio.js';
+import { SizeError } from '../src/errors.js';
+
+describe('atomicWrite', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-fileio-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('should create parent directories and leave no temp files behind', async () => {
+    const output = join(root, 'a', 'b', 'out.txt');
+
+    await atomicWrite(output, 'hello\n');
+
+    expect(await fs.readFile(output, 'utf-8')).toBe('hello\n');
+    expect(await fs.readdir(join(root, 'a', 'b'))).toEqual(['out.txt']);
+  });
+
+  it('should stage the temp file in tempDir when given', async () => {
+    const tempDir = join(root, 'staging');
+    await fs.mkdir(tempDir);
+    const output = join(root, 'out', 'out.txt');
+
+    await atomicWrite(output, 'hello\n', { tempDir });
+
+    expect(await fs.readFile(output, 'utf-8')).toBe('hello\n');
+    expect(await fs.readdir(join(root, 'out'))).toEqual(['out.txt']);
+    expect(await fs.readdir(tempDir)).toEqual([]);
+  });
+
+  it('should write the same output with and without fsync and directory fsync', async () => {
+    const modes = [{}, { fsync: false }, { fsyncDir: true }, { fsync: false, fsyncDir: true }];
+    async function* chunks(): AsyncGenerator<string> {
+      yield 'streamed ';
+      yield 'hello\n';
+    }
+
+    for (const [index, options] of modes.entries()) {
+      const dir = join(root, String(index));
+      await atomicWrite(join(dir, 'whole.txt'), 'hello\n', options);
+      await atomicWrite(join(dir, 'streamed.txt'), chunks(), options);
+
+      expect(await fs.readFile(join(dir, 'whole.txt'), 'utf-8')).toBe('hello\n');
+      expect(await fs.readFile(join(dir, 'streamed.txt'), 'utf-8')).toBe('streamed hello\n');
+      expect((await fs.readdir(dir)).sort()).toEqual(['streamed.txt', 'whole.txt']);
+    }
+  });
+
+  it.skipIf(process.platform === 'win32')('should keep the permissions of a file it overwrites', async () => {
+    const output = join(root, 'out.txt');
+    await fs.writeFile(output, 'old\n', 'utf-8');
+    await fs.chmod(output, 0o750);
+
+    await atomicWrite(output, 'new\n');
+
+    expect(await fs.readFile(output, 'utf-8')).toBe('new\n');
+    expect((await fs.stat(output)).mode & 0o777).toBe(0o750);
+  });
+
+  it.skipIf(process.platform === 'win32')('should write into a named pipe instead of replacing it', async () => {
+    const fifo = join(root, 'out.fifo');
+    execFileSync('mkfifo', [fifo]);
+
+    // Opening a FIFO blocks until both ends are open, so read while writing
+    const [received] = await Promise.all([fs.readFile(fifo, 'utf-8'), atomicWrite(fifo, 'hello\n')]);
+
+    expect(received).toBe('hello\n');
+    expect((await fs.stat(fifo)).isFIFO()).toBe(true);
+    expect(await fs.readdir(root)).toEqual(['out.fifo']);
+  });
+});
+
+describe('withRetries', () => {
+  /**
+   * Builds an operation that fails with `code` the given number of times, then succeeds.
+   */
+  function failing(times: number, code: string): { operation: () => Promise<string>; calls: () => number } {
+    let calls = 0;
+    return {
+      operation: async () => {
+        calls++;
+        if (calls <= times) {
+          throw Object.assign(new Error(`${code} on attempt ${calls}`), { code });
+        }
+        return 'done';
+      },
+      calls: () => calls,
+    };
+  }
+
+  it('should retry transient errors until the operation succeeds', async () => {
+    const { operation, calls } = failing(2, 'EBUSY');
+
+    expect(await withRetries(operation, 3, 1)).toBe('done');
+    expect(calls()).toBe(3);
+  });
+
+  it('should give up after the configured number of retries', async () => {
+    const { operation, calls } = failing(5, 'EIO');
+
+    await expect(withRetries(operation, 2, 1)).rejects.toThrow('EIO on attempt 3');
+    expect(calls()).toBe(3);
+  });
+
+  it('should not retry errors that are not transient', async () => {
+    const { operation, calls } = failing(1, 'ENOENT');
+
+    await expect(withRetries(operation, 3, 1)).rejects.toThrow('ENOENT on attempt 1');
+    expect(calls()).toBe(1);
+  });
+});
+
+describe('checkSize', () => {
+  let root: string;
+  let path: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-size-'));
+    path = join(root, 'input.txt');
+    await fs.writeFile(path, '0123456789');
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('should pass a file under the limit and return its stats', async () => {
+    expect((await checkSize(path, 11)).size).toBe(10);
+  });
+
+  it('should pass a file exactly at the limit', async () => {
+    expect((await checkSize(path, 10)).size).toBe(10);
+  });
+
+  it('should reject a file over the limit with its size and the limit', async () => {
+    const error = await checkSize(path, 9).catch((caught) => caught);
+
+    expect(error).toBeInstanceOf(SizeError);
+    expect(error).toMatchObject({ path, size: 10, max: 9 });
+    expect(error.message).toBe('File too large: 10 bytes exceeds maximum of 9 bytes');
+  });
+});
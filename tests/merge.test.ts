@@ -0,0 +1,766 @@
+/**
+ * Tests for the merge command.
+ */
+
+import { describe, it, expect, beforeEach, afterEach, spyOn } from 'bun:test';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { createHash } from 'crypto';
+import { gunzipSync, gzipSync } from 'zlib';
+import { format } from 'date-fns';
+import {
+  executeMerge,
+  executeMergeGroups,
+  formatMergeJson,
+  classifyFilename,
+  countSegments,
+  sortPaths,
+} from '../src/commands/merge.js';
+
+/**
+ * Writes a file, creating parent directories as needed.
+ */
+async function writeFile(path: string, content: string): Promise<void> {
+  await fs.mkdir(join(path, '..'), { recursive: true });
+  await fs.writeFile(path, content, 'utf-8');
+}
+
+describe('executeMerge', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-merge-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('should merge nested segments into the dashed day file by default', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+    expect(await fs.readdir(dayDir)).toEqual(['2025-01-27.txt']);
+    expect(result.segments).toBe(2);
+    expect(result.lines).toBe(2);
+  });
+
+  it('should apply --date-format to the inferred output name', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+
+    const result = await executeMerge({
+      patterns: [join(dayDir, '*.txt')],
+      noDelete: true,
+      dateFormat: 'yyyyMMdd',
+    });
+
+    expect(result.outputPath).toBe(join(dayDir, '20250127.txt'));
+  });
+
+  it('should reject a date format that produces a path separator', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+
+    await expect(
+      executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true, dateFormat: 'yyyy/MM/dd' })
+    ).rejects.toThrow('Invalid date format');
+  });
+
+  it('should ignore .idx, .jsonl, and .md siblings matched by a broad glob', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '061901-070000.idx'), '06:19:01\t1\n');
+    await writeFile(join(dayDir, '111901-120000.jsonl'), '{"absolute":null,"text":"x","had_timestamp":false}\n');
+    await writeFile(join(dayDir, '111901-120000.md'), '# notes\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*')], noDelete: false });
+
+    expect(result.files).toEqual([join(dayDir, '061901-070000.txt')]);
+    expect((await fs.readdir(dayDir)).sort()).toEqual([
+      '061901-070000.idx',
+      '111901-120000.jsonl',
+      '111901-120000.md',
+      '2025-01-27.txt',
+    ]);
+    await expect(executeMerge({ patterns: [join(dayDir, '*.md')] })).rejects.toThrow('No transcript segments');
+  });
+
+  it('should report a checksum matching the written output', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true, checksum: true });
+
+    const written = await fs.readFile(result.outputPath);
+    expect(result.checksum?.sha256).toEqual([createHash('sha256').update(written).digest('hex')]);
+    expect(result.checksum?.sourceBytes).toBe(14 + 16);
+  });
+
+  it('should report checksums of the bytes on disk for compressed and split outputs', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), `06:19:01 ${'a'.repeat(40)}\n`);
+    await writeFile(join(dayDir, '071901-080000.txt'), `07:19:01 ${'b'.repeat(40)}\n`);
+    const patterns = [join(dayDir, '*.txt')];
+    const onDisk = async (path: string) => createHash('sha256').update(await fs.readFile(path)).digest('hex');
+
+    for (const options of [{ compress: true }, { compress: true, stream: true }, { maxOutputSize: '60' }]) {
+      const result = await executeMerge({ patterns, noDelete: true, checksum: true, ...options });
+      expect(result.checksum?.sha256).toEqual(await Promise.all(result.parts.map(onDisk)));
+      await Promise.all(result.parts.map((part) => fs.rm(part)));
+    }
+  });
+
+  it('should reject an --output that is an existing directory without touching the sources', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+
+    await expect(executeMerge({ patterns: [join(dayDir, '*.txt')], output: root })).rejects.toThrow(
+      `Output path '${root}' is an existing directory`
+    );
+    expect(await fs.readdir(dayDir)).toEqual(['061901-070000.txt']);
+  });
+
+  it('should pass --verify for buffered, streamed, split, and compressed merges', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    const write = async () => {
+      await writeFile(join(dayDir, '061901-070000.txt'), `06:19:01 ${'a'.repeat(40)}`);
+      await writeFile(join(dayDir, '071901-080000.txt'), `07:19:01 ${'b'.repeat(40)}\r\n`);
+    };
+    const patterns = [join(dayDir, '*.txt')];
+
+    for (const options of [{}, { stream: true }, { maxOutputSize: '60' }, { compress: true }]) {
+      await write();
+      const result = await executeMerge({ patterns, output: join(root, 'merged.txt'), verify: true, ...options });
+      expect(result.deleted).toBe(true);
+      expect(await fs.readdir(dayDir)).toEqual([]);
+    }
+  });
+
+  it('should split the output into parts at segment boundaries with --max-output-size', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    const first = `06:19:01 ${'a'.repeat(40)}\n`;
+    const second = `07:19:01 ${'b'.repeat(40)}\n`;
+    const third = `08:19:01 ${'c'.repeat(40)}\n`;
+    await writeFile(join(dayDir, '061901-070000.txt'), first);
+    await writeFile(join(dayDir, '071901-080000.txt'), second);
+    await writeFile(join(dayDir, '081901-090000.txt'), third);
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false, maxOutputSize: '100' });
+
+    expect(result.parts).toEqual([join(dayDir, '2025-01-27.part1.txt'), join(dayDir, '2025-01-27.part2.txt')]);
+    expect(await fs.readFile(result.parts[0], 'utf-8')).toBe(first + second);
+    expect(await fs.readFile(result.parts[1], 'utf-8')).toBe(third);
+    expect((await fs.readdir(dayDir)).sort()).toEqual(['2025-01-27.part1.txt', '2025-01-27.part2.txt']);
+  });
+
+  it('should summarize sources, output, deletion, and span as JSON', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true });
+
+    expect(JSON.parse(formatMergeJson(result))).toEqual({
+      sources: [join(dayDir, '061901-070000.txt'), join(dayDir, '111901-120000.txt')],
+      output: join(dayDir, '2025-01-27.txt'),
+      parts: [join(dayDir, '2025-01-27.txt')],
+      deleted: false,
+      span: { start: '2025-01-27 06:19:01', end: '2025-01-27 12:00:00' },
+      warnings: [],
+    });
+  });
+
+  it('should list the written parts, checksums, and warnings in the JSON summary', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), `06:19:01 ${'a'.repeat(40)}\n`);
+    await writeFile(join(dayDir, '071901-080000.txt'), `07:19:01 ${'b'.repeat(40)}\n`);
+    const patterns = [join(dayDir, '*.txt')];
+
+    const split = JSON.parse(formatMergeJson(await executeMerge({ patterns, noDelete: true, maxOutputSize: '60' })));
+    expect(split.parts).toEqual([join(dayDir, '2025-01-27.part1.txt'), join(dayDir, '2025-01-27.part2.txt')]);
+    expect(split.sha256).toBeUndefined();
+
+    // A newer output is overwritten with a warning, which JSON mode must not lose
+    const output = join(root, 'merged.txt');
+    await fs.writeFile(output, 'edited by hand\n', 'utf-8');
+    const later = new Date(Date.now() + 60_000);
+    await fs.utimes(output, later, later);
+    const summed = JSON.parse(
+      formatMergeJson(await executeMerge({ patterns, output, noDelete: true, checksum: true }))
+    );
+    const written = await fs.readFile(output);
+    expect(summed.parts).toEqual([output]);
+    expect(summed.sha256).toEqual([createHash('sha256').update(written).digest('hex')]);
+    expect(summed.warnings.map((warning: { kind: string }) => warning.kind)).toEqual(['newerOutput']);
+  });
+
+  it('should resolve relative patterns against --base-dir rather than the cwd', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+    expect(process.cwd()).not.toBe(root);
+
+    const result = await executeMerge({ patterns: ['2025/01/27/*.txt'], baseDir: root, noDelete: false });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+  });
+
+  it('should refuse to overwrite an output newer than every source with --safe', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '2025-01-27.txt'), 'edited by hand\n');
+    const sourceTime = new Date(Date.now() - 60_000);
+    await fs.utimes(join(dayDir, '061901-070000.txt'), sourceTime, sourceTime);
+    const pattern = join(dayDir, '??????-??????.txt');
+
+    await expect(executeMerge({ patterns: [pattern], noDelete: false, safe: true })).rejects.toThrow('is newer than every source');
+    expect(await fs.readFile(join(dayDir, '2025-01-27.txt'), 'utf-8')).toBe('edited by hand\n');
+
+    const result = await executeMerge({ patterns: [pattern], noDelete: false });
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['newerOutput']);
+  });
+
+  it.skipIf(process.platform === 'win32')('should merge into a named pipe', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    const sourceTime = new Date(Date.now() - 60_000);
+    await fs.utimes(join(dayDir, '061901-070000.txt'), sourceTime, sourceTime);
+    const fifo = join(root, 'out.fifo');
+    execFileSync('mkfifo', [fifo]);
+    const args = { patterns: [join(dayDir, '*.txt')], output: fifo, noDelete: true };
+
+    await expect(executeMerge({ ...args, compress: true })).rejects.toThrow('--compress cannot be used with output');
+    await expect(executeMerge({ ...args, verify: true })).rejects.toThrow('--verify cannot be used with output');
+    // Opening a FIFO blocks until both ends are open, so read while merging
+    const [received, result] = await Promise.all([fs.readFile(fifo, 'utf-8'), executeMerge(args)]);
+
+    expect(received).toBe('06:19:01 First\n');
+    // The pipe is newer than the source, but it holds no earlier merge to protect
+    expect(result.warnings).toEqual([]);
+  });
+
+  it('should warn about an empty segment', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '071901-080000.txt'), '');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true });
+
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['emptyInput']);
+    expect(result.warnings[0].message).toContain('071901-080000.txt is empty');
+  });
+
+  it('should keep the given order with --preserve-order while inferring the output name', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    const names = ['061901-070000.txt', '071901-080000.txt', '081901-090000.txt'];
+    for (const name of names) {
+      await writeFile(join(dayDir, name), `${name}\n`);
+    }
+
+    const patterns = [...names].reverse().map((name) => join(dayDir, name));
+    const result = await executeMerge({ patterns, noDelete: true, preserveOrder: true });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(
+      '081901-090000.txt\n071901-080000.txt\n061901-070000.txt\n'
+    );
+  });
+
+  it('should recognize segments with a single tag before the extension', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '111901-120000.final.txt'), '11:19:01 Second\n');
+    await writeFile(join(dayDir, '061901-111901.v2.txt'), '06:19:01 First\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+  });
+
+  it('should fail with --expect-delete when the only match is the output', async () => {
+    const output = join(root, '2025', '01', '27', '061901-070000.txt');
+    await writeFile(output, '06:19:01 First\n');
+
+    await expect(
+      executeMerge({ patterns: [output], output, noDelete: false, expectDelete: true })
+    ).rejects.toThrow('No source files would be deleted');
+    expect(await fs.readFile(output, 'utf-8')).toBe('06:19:01 First\n');
+  });
+
+  it('should fail with --min-segments when the glob matches too few files', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+
+    await expect(
+      executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false, minSegments: 2 })
+    ).rejects.toThrow('Found 1 segment(s) to merge but --min-segments requires at least 2');
+    expect(await fs.readdir(dayDir)).toEqual(['061901-070000.txt']);
+  });
+
+  it('should give a nested segment outside a day directory the date of its flat siblings', async () => {
+    const inbox = join(root, 'inbox');
+    await writeFile(join(root, '20250127_061901_070000.txt'), '06:19:01 Flat\n');
+    await writeFile(join(inbox, '051901-060000.txt'), '05:19:01 Dateless\n');
+
+    const result = await executeMerge({
+      patterns: [join(root, '*.txt'), join(inbox, '*.txt')],
+      output: join(root, 'merged.txt'),
+      noDelete: true,
+    });
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('05:19:01 Dateless\n06:19:01 Flat\n');
+    expect(result.span).toEqual({
+      start: new Date(2025, 0, 27, 5, 19, 1),
+      end: new Date(2025, 0, 27, 7, 0, 0),
+      hasDate: true,
+    });
+  });
+
+  it('should drop byte order marks from segments after the first', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '\uFEFF11:19:01 Second\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false });
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+  });
+
+  it('should name the segment that is not valid UTF-8', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await fs.writeFile(join(dayDir, '111901-120000.txt'), Buffer.from([0x31, 0xff, 0xfe, 0x0a]));
+
+    await expect(executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false })).rejects.toThrow(
+      `${join(dayDir, '111901-120000.txt')} is not valid UTF-8`
+    );
+  });
+
+  describe('output location for mixed flat and nested segments', () => {
+    async function writeMixed(): Promise<{ dayDir: string; patterns: string[] }> {
+      const dayDir = join(root, '2025', '01', '27');
+      await writeFile(join(root, '20250127_051901_060000.txt'), '05:19:01 Flat\n');
+      await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 Nested\n');
+      return { dayDir, patterns: [join(root, '*.txt'), join(dayDir, '*.txt')] };
+    }
+
+    it('should place the output next to the earliest file with auto', async () => {
+      const { patterns } = await writeMixed();
+
+      const result = await executeMerge({ patterns, noDelete: true, outputLocation: 'auto' });
+
+      expect(result.outputPath).toBe(join(root, '2025-01-27.txt'));
+    });
+
+    it('should place the output in the nested day directory with nested', async () => {
+      const { dayDir, patterns } = await writeMixed();
+
+      const result = await executeMerge({ patterns, noDelete: true, outputLocation: 'nested' });
+
+      expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+      expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('05:19:01 Flat\n06:19:01 Nested\n');
+    });
+
+    it('should place the output in the current directory with cwd', async () => {
+      const { patterns } = await writeMixed();
+      const cwd = await fs.mkdtemp(join(root, 'cwd-'));
+      const previous = process.cwd();
+      process.chdir(cwd);
+      try {
+        const result = await executeMerge({ patterns, noDelete: true, outputLocation: 'cwd' });
+
+        expect(await fs.realpath(result.outputPath)).toBe(join(await fs.realpath(cwd), '2025-01-27.txt'));
+      } finally {
+        process.chdir(previous);
+      }
+    });
+
+    it('should resolve cwd against the base directory when one is given', async () => {
+      const { patterns } = await writeMixed();
+      const base = await fs.mkdtemp(join(root, 'base-'));
+
+      const result = await executeMerge({ patterns, noDelete: true, outputLocation: 'cwd', baseDir: base });
+
+      expect(result.outputPath).toBe(join(base, '2025-01-27.txt'));
+    });
+  });
+
+  it('should stat and resolve each source only once', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    const names = ['061901-070000.txt', '071901-080000.txt', '081901-090000.txt'];
+    for (const name of names) {
+      await writeFile(join(dayDir, name), `${name}\n`);
+    }
+
+    const stat = spyOn(fs, 'stat');
+    const realpath = spyOn(fs, 'realpath');
+    try {
+      const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false });
+
+      expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(names.join('\n') + '\n');
+      for (const source of result.files) {
+        expect(stat.mock.calls.filter(([path]) => path === source)).toHaveLength(1);
+        expect(realpath.mock.calls.filter(([path]) => path === source)).toHaveLength(1);
+      }
+    } finally {
+      stat.mockRestore();
+      realpath.mockRestore();
+    }
+  });
+
+  it('should renumber speakers so segments do not collide with --renumber-speakers', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 Speaker 1: Hi\n06:19:05 Speaker 2: Hello\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Speaker 1: Back\n11:19:09 Speaker 2: Yes\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false, renumberSpeakers: true });
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(
+      '06:19:01 Speaker 1: Hi\n06:19:05 Speaker 2: Hello\n11:19:01 Speaker 3: Back\n11:19:09 Speaker 4: Yes\n'
+    );
+  });
+
+  it.skipIf(process.platform === 'win32')('should run the post-merge command with the output path', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    const copy = join(root, 'copy of output.txt');
+
+    const result = await executeMerge({
+      patterns: [join(dayDir, '*.txt')],
+      noDelete: false,
+      postMergeCmd: `cp {output} '${copy}'`,
+    });
+
+    expect(result.postMergeStatus).toBe(0);
+    expect(await fs.readFile(copy, 'utf-8')).toBe('06:19:01 First\n');
+  });
+
+  it.skipIf(process.platform === 'win32')('should report a failing post-merge command', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false, postMergeCmd: 'exit 3' });
+
+    expect(result.postMergeStatus).toBe(3);
+    expect(await fs.readFile(join(dayDir, '2025-01-27.txt'), 'utf-8')).toBe('06:19:01 First\n');
+  });
+
+  it('should skip glob matches deeper than --max-depth', async () => {
+    await writeFile(join(root, '2025', '01', '27', '061901-070000.txt'), '06:19:01 Kept\n');
+    await writeFile(join(root, '2025', '01', '27', 'drafts', '111901-120000.txt'), '11:19:01 Draft\n');
+
+    const result = await executeMerge({
+      patterns: [join(root, '**', '*.txt')],
+      output: join(root, 'merged.txt'),
+      noDelete: true,
+      maxDepth: 3,
+    });
+
+    expect(result.files).toEqual([join(root, '2025', '01', '27', '061901-070000.txt')]);
+  });
+
+  it('should name the inferred output with --start and --end', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+
+    const result = await executeMerge({ patterns: [dayDir], noDelete: true, start: '06:00:00', end: '12:30:00' });
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27_060000-123000.txt'));
+
+    const startOnly = await executeMerge({ patterns: [dayDir], noDelete: true, start: '06:00:00' });
+    expect(startOnly.outputPath).toBe(join(dayDir, '2025-01-27_060000-120000.txt'));
+  });
+
+  it('should write one output per run of segments with --group-by-gap', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(dayDir, '093500-100000.txt'), '09:35:00 B\n');
+    await writeFile(join(dayDir, '120000-123000.txt'), '12:00:00 C\n');
+    await writeFile(join(dayDir, '124000-130000.txt'), '12:40:00 D\n');
+
+    const outcomes = await executeMergeGroups({ patterns: [dayDir], noDelete: false, groupByGap: '1h' });
+
+    expect(outcomes.map((outcome) => outcome.outputPath)).toEqual([
+      join(dayDir, '2025-01-27_090000-100000.txt'),
+      join(dayDir, '2025-01-27_120000-130000.txt'),
+    ]);
+    expect(await fs.readFile(outcomes[0].outputPath, 'utf-8')).toBe('09:00:00 A\n09:35:00 B\n');
+    expect(await fs.readFile(outcomes[1].outputPath, 'utf-8')).toBe('12:00:00 C\n12:40:00 D\n');
+    expect((await fs.readdir(dayDir)).sort()).toEqual([
+      '2025-01-27_090000-100000.txt',
+      '2025-01-27_120000-130000.txt',
+    ]);
+  });
+
+  it('should stream the same output as the buffered merge', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '\uFEFF09:00:00 Speaker 1: A\n09:10:00 Speaker 2: B');
+    await writeFile(join(dayDir, '100000-103000.txt'), '');
+    await writeFile(join(dayDir, '110000-113000.txt'), '\uFEFF11:00:00 Speaker 1: C\n');
+    await writeFile(join(dayDir, '120000-123000.txt'), '12:00:00 Speaker 2: D');
+    const options = { patterns: [dayDir], noDelete: true, checksum: true, renumberSpeakers: true, dateHeader: true };
+
+    const buffered = await executeMerge({ ...options, output: join(root, 'buffered.txt') });
+    const streamed = await executeMerge({ ...options, output: join(root, 'streamed.txt'), stream: true });
+
+    expect(await fs.readFile(streamed.outputPath, 'utf-8')).toBe(await fs.readFile(buffered.outputPath, 'utf-8'));
+    expect(streamed.lines).toBe(buffered.lines);
+    expect(streamed.checksum).toEqual(buffered.checksum);
+  });
+
+  it('should keep each segment\'s line endings unless --eol rewrites them', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\r\n09:10:00 B');
+    await writeFile(join(dayDir, '100000-103000.txt'), '10:00:00 C\n10:10:00 D\n');
+
+    const kept = await executeMerge({ patterns: [dayDir], noDelete: true, output: join(root, 'kept.txt') });
+    const lf = await executeMerge({ patterns: [dayDir], noDelete: true, output: join(root, 'lf.txt'), eol: 'lf' });
+
+    expect(await fs.readFile(kept.outputPath, 'utf-8')).toBe('09:00:00 A\r\n09:10:00 B\r\n10:00:00 C\n10:10:00 D\n');
+    expect(await fs.readFile(lf.outputPath, 'utf-8')).toBe('09:00:00 A\n09:10:00 B\n10:00:00 C\n10:10:00 D\n');
+  });
+
+  it('should leave out files matching --exclude', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(dayDir, '100000-103000.txt'), '10:00:00 B\n');
+    await writeFile(join(dayDir, '2025-01-27.txt'), '08:00:00 Earlier merge\n');
+    const output = join(root, 'merged.txt');
+    const exclude = ['*2025-01-27.txt'];
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*')], noDelete: true, output, exclude });
+
+    expect(result.files).toEqual([join(dayDir, '090000-093000.txt'), join(dayDir, '100000-103000.txt')]);
+    expect(await fs.readFile(output, 'utf-8')).toBe('09:00:00 A\n10:00:00 B\n');
+  });
+
+  it('should count the matched segments without merging for --count-only', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(dayDir, '100000-103000.txt'), '10:00:00 B\n');
+
+    expect(await countSegments({ patterns: [dayDir, join(dayDir, '*.txt')], noDelete: false })).toBe(2);
+    expect((await fs.readdir(dayDir)).sort()).toEqual(['090000-093000.txt', '100000-103000.txt']);
+    await expect(countSegments({ patterns: [join(root, 'none', '*.txt')], noDelete: false })).rejects.toThrow();
+  });
+
+  it('should start the output with one date header', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(dayDir, '100000-103000.txt'), '10:00:00 B\n');
+
+    const result = await executeMerge({ patterns: [dayDir], noDelete: true, dateHeader: true });
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('# 2025-01-27\n\n09:00:00 A\n10:00:00 B\n');
+
+    // A first segment that already carries the header is not given a second one
+    await writeFile(join(dayDir, '090000-093000.txt'), '# 2025-01-27\n\n09:00:00 A\n');
+    const output = join(root, 'notes.txt');
+    await executeMerge({ patterns: [dayDir], output, noDelete: true, dateHeader: true });
+    expect(await fs.readFile(output, 'utf-8')).toBe('# 2025-01-27\n\n09:00:00 A\n10:00:00 B\n');
+  });
+
+  it('should fail instead of creating the output directory with noCreateDirs', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    const output = join(root, 'missing', 'merged.txt');
+
+    await expect(executeMerge({ patterns: [dayDir], output, noDelete: false, noCreateDirs: true })).rejects.toThrow(
+      `Output directory does not exist: ${join(root, 'missing')}`
+    );
+    expect(await fs.readdir(root)).toEqual(['2025']);
+    expect(await fs.readdir(dayDir)).toEqual(['090000-093000.txt']);
+  });
+
+  it('should write one output per day under --output-root', async () => {
+    await writeFile(join(root, '2025', '01', '27', '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(root, '2025', '01', '27', '100000-103000.txt'), '10:00:00 B\n');
+    await writeFile(join(root, '2025', '01', '28', '080000-083000.txt'), '08:00:00 C\n');
+    const outputRoot = join(root, 'out');
+
+    const outcomes = await executeMergeGroups({
+      patterns: [join(root, '2025', '01', '**', '*.txt')],
+      noDelete: true,
+      outputRoot,
+    });
+
+    expect(outcomes.map((outcome) => outcome.outputPath)).toEqual([
+      join(outputRoot, '2025', '01', '27', '2025-01-27.txt'),
+      join(outputRoot, '2025', '01', '28', '2025-01-28.txt'),
+    ]);
+    expect(await fs.readFile(outcomes[0].outputPath, 'utf-8')).toBe('09:00:00 A\n10:00:00 B\n');
+    expect(await fs.readFile(outcomes[1].outputPath, 'utf-8')).toBe('08:00:00 C\n');
+  });
+
+  it('should infer the date from a day directory in a custom format', async () => {
+    const dayDir = join(root, '2025_01_27');
+    await writeFile(join(dayDir, '090000-093000.txt'), '09:00:00 A\n');
+    await writeFile(join(dayDir, '100000-103000.txt'), '10:00:00 B\n');
+
+    const result = await executeMerge({ patterns: [dayDir], noDelete: true, dayDirFormat: 'yyyy_MM_dd' });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('09:00:00 A\n10:00:00 B\n');
+  });
+
+  it('should decompress .gz segments into a plain output', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await fs.mkdir(dayDir, { recursive: true });
+    await fs.writeFile(join(dayDir, '061901-070000.txt.gz'), gzipSync('06:19:01 First\n'));
+    await fs.writeFile(join(dayDir, '111901-120000.txt.gz'), gzipSync('11:19:01 Second\n'));
+
+    const result = await executeMerge({ patterns: [dayDir], noDelete: false });
+
+    expect(result.outputPath).toBe(join(dayDir, '2025-01-27.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+    expect(await fs.readdir(dayDir)).toEqual(['2025-01-27.txt']);
+  });
+
+  it('should gzip the output when it ends in .gz', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+    const output = join(root, 'combined.txt.gz');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], output, noDelete: false });
+
+    expect(result.outputPath).toBe(output);
+    expect(gunzipSync(await fs.readFile(output)).toString('utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+    expect(await fs.readdir(dayDir)).toEqual([]);
+  });
+
+  it('should expand a directory argument to its transcript segments', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Second\n');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, 'notes.txt'), 'not a segment\n');
+
+    const result = await executeMerge({ patterns: [dayDir], noDelete: false });
+
+    expect(result.files).toEqual([join(dayDir, '061901-070000.txt'), join(dayDir, '111901-120000.txt')]);
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 First\n11:19:01 Second\n');
+    expect((await fs.readdir(dayDir)).sort()).toEqual(['2025-01-27.txt', 'notes.txt']);
+  });
+
+  it('should skip update index files matched by the pattern', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '061901-070000.txt'), '06:19:01 First\n');
+    await writeFile(join(dayDir, 'index.txt'), '# plaud-tm index\nsomewhere\t2025-01-27 06:19:01\t2025-01-27 07:00:00\n');
+
+    const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true });
+
+    expect(result.files).toEqual([join(dayDir, '061901-070000.txt')]);
+  });
+
+  it('should merge dotfile segments only with --hidden', async () => {
+    const dayDir = join(root, '2025', '01', '27');
+    await writeFile(join(dayDir, '.061901-070000.txt'), '06:19:01 Hidden\n');
+    await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Visible\n');
+
+    const visible = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: true });
+    expect(visible.files).toEqual([join(dayDir, '111901-120000.txt')]);
+
+    // The first merge left 2025-01-27.txt behind; match segment names only
+    const all = await executeMerge({ patterns: [join(dayDir, '*-??????.txt')], noDelete: true, hidden: true });
+    expect(all.files).toEqual([join(dayDir, '.061901-070000.txt'), join(dayDir, '111901-120000.txt')]);
+    expect(await fs.readFile(all.outputPath, 'utf-8')).toBe('06:19:01 Hidden\n11:19:01 Visible\n');
+  });
+
+  describe('symlinked sources', () => {
+    it.skipIf(process.platform === 'win32')('should remove the symlink but keep its target', async () => {
+      const dayDir = join(root, '2025', '01', '27');
+      const target = join(root, 'store', 'segment.txt');
+      await writeFile(target, '06:19:01 Linked\n');
+      await writeFile(join(dayDir, '111901-120000.txt'), '11:19:01 Real\n');
+      await fs.mkdir(dayDir, { recursive: true });
+      await fs.symlink(target, join(dayDir, '061901-070000.txt'));
+
+      const result = await executeMerge({ patterns: [join(dayDir, '*.txt')], noDelete: false });
+
+      expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('06:19:01 Linked\n11:19:01 Real\n');
+      expect(await fs.readdir(dayDir)).toEqual(['2025-01-27.txt']);
+      expect(await fs.readFile(target, 'utf-8')).toBe('06:19:01 Linked\n');
+    });
+
+    it.skipIf(process.platform === 'win32')('should not follow a symlink that resolves to the output', async () => {
+      const dayDir = join(root, '2025', '01', '27');
+      const real = join(dayDir, '061901-070000.txt');
+      const link = join(dayDir, '111901-120000.txt');
+      const output = join(dayDir, '2025-01-27.txt');
+      await writeFile(real, '06:19:01 First\n');
+      await writeFile(output, 'previous merge\n');
+      await fs.symlink(output, link);
+
+      const result = await executeMerge({ patterns: [real, link], noDelete: false });
+
+      expect(result.files).toEqual([real]);
+      expect(await fs.readFile(output, 'utf-8')).toBe('06:19:01 First\n');
+      expect((await fs.lstat(link)).isSymbolicLink()).toBe(true);
+    });
+  });
+});
+
+describe('classifyFilename', () => {
+  it('should read the date and times from a flat name', () => {
+    const kind = classifyFilename(join('exports', '20250127_061901_070000.txt'));
+
+    expect(kind?.format).toBe('flat');
+    expect(kind?.date && format(kind.date, 'yyyy-MM-dd')).toBe('2025-01-27');
+    expect(kind && format(kind.start, 'HH:mm:ss')).toBe('06:19:01');
+    expect(kind?.end && format(kind.end, 'HH:mm:ss')).toBe('07:00:00');
+  });
+
+  it('should take a nested name\'s date from its day directory', () => {
+    const kind = classifyFilename(join('2025', '01', '27', '061901-070000.txt'));
+
+    expect(kind?.format).toBe('nested');
+    expect(kind?.date && format(kind.date, 'yyyy-MM-dd')).toBe('2025-01-27');
+    expect(kind && format(kind.start, 'HH:mm:ss')).toBe('06:19:01');
+    expect(classifyFilename(join('inbox', '061901-070000.txt'))?.date).toBeNull();
+  });
+
+  it('should return null for names that are not segments', () => {
+    expect(classifyFilename('notes.txt')).toBeNull();
+    expect(classifyFilename('20250127_251901_070000.txt')).toBeNull();
+  });
+});
+
+describe('sortPaths', () => {
+  it('should order flat names by date, then start time', () => {
+    const paths = ['20250128_080000_083000.txt', '20250127_120000_123000.txt', '20250127_061901_070000.txt'];
+
+    expect(sortPaths(paths)).toEqual([
+      '20250127_061901_070000.txt',
+      '20250127_120000_123000.txt',
+      '20250128_080000_083000.txt',
+    ]);
+  });
+
+  it('should order nested names by day directory and drop duplicates', () => {
+    const late = join('2025', '01', '27', '120000-123000.txt');
+    const early = join('2025', '01', '27', '061901-070000.txt');
+    const nextDay = join('2025', '01', '28', '010000-020000.txt');
+
+    expect(sortPaths([nextDay, late, early, late])).toEqual([early, late, nextDay]);
+  });
+
+  it('should interleave flat and nested names and put undated segments last', () => {
+    const nested = join('2025', '01', '27', '090000-093000.txt');
+    const undated = join('inbox', '050000-060000.txt');
+    const flat = '20250126_230000_233000.txt';
+    const sameDay = '20250127_080000_083000.txt';
+
+    expect(sortPaths([undated, nested, sameDay, flat])).toEqual([flat, sameDay, nested, undated]);
+    expect(() => sortPaths(['notes.txt'])).toThrow();
+  });
+});
@@ -0,0 +1,40 @@
+/**
+ * Tests for ownership spec resolution.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { resolveOwner, AccountDatabases } from '../src/utils/ownership.js';
+
+describe('resolveOwner', () => {
+  let root: string;
+  let databases: AccountDatabases;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-ownership-'));
+    databases = { passwd: join(root, 'passwd'), group: join(root, 'group') };
+    await fs.writeFile(databases.passwd, 'root:x:0:0::/root:/bin/sh\nalice:x:1001:1001::/home/alice:/bin/sh\n');
+    await fs.writeFile(databases.group, 'root:x:0:\ntranscripts:x:2000:alice\n');
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('should resolve user and group names to ids', async () => {
+    expect(await resolveOwner('alice:transcripts', databases)).toEqual({ uid: 1001, gid: 2000 });
+  });
+
+  it('should accept numeric ids and leave an omitted side unchanged', async () => {
+    expect(await resolveOwner('1234', databases)).toEqual({ uid: 1234, gid: -1 });
+    expect(await resolveOwner(':transcripts', databases)).toEqual({ uid: -1, gid: 2000 });
+  });
+
+  it('should reject unknown names and malformed specs', async () => {
+    await expect(resolveOwner('bob:transcripts', databases)).rejects.toThrow("Unknown user 'bob'");
+    await expect(resolveOwner('alice:staff', databases)).rejects.toThrow("Unknown group 'staff'");
+    await expect(resolveOwner('a:b:c', databases)).rejects.toThrow('Use user:group, user, or :group');
+  });
+});
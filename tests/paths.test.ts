@@ -0,0 +1,20 @@
+/**
+ * Tests for path display helpers.
+ */
+
+import { describe, it, expect } from 'bun:test';
+import { displayPath } from '../src/utils/paths.js';
+
+describe('displayPath', () => {
+  it('should convert Windows separators to forward slashes when requested', () => {
+    expect(displayPath('2025\\01\\27\\2025-01-27.txt', true, '\\')).toBe('2025/01/27/2025-01-27.txt');
+  });
+
+  it('should leave paths untouched by default', () => {
+    expect(displayPath('2025\\01\\27\\2025-01-27.txt', false, '\\')).toBe('2025\\01\\27\\2025-01-27.txt');
+  });
+
+  it('should not rewrite backslashes on platforms that use forward slashes', () => {
+    expect(displayPath('odd\\name.txt', true, '/')).toBe('odd\\name.txt');
+  });
+});
@@ -0,0 +1,55 @@
+/**
+ * Tests for the rename command.
+ */
+
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { executeRename } from '../src/commands/rename.js';
+import { fileExists } from '../src/utils/validation.js';
+
+describe('executeRename', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-rename-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('should move a file to the nested name for its first and last timestamps', async () => {
+    const source = join(root, 'meeting notes (final).txt');
+    await fs.writeFile(source, '18:06:13 Speaker 1\nHello\n18:15:30 Speaker 2\n', 'utf-8');
+    const target = join(root, 'out', '2024', '03', '15', '180613-181530.txt');
+
+    const result = await executeRename({
+      patterns: [join(root, '*.txt')],
+      date: '2024-03-15',
+      outputDir: join(root, 'out'),
+      flat: false,
+    });
+
+    expect(result.moves).toEqual([{ source, target }]);
+    expect(await fileExists(source)).toBe(false);
+    expect(await fs.readFile(target, 'utf-8')).toBe('18:06:13 Speaker 1\nHello\n18:15:30 Speaker 2\n');
+  });
+
+  it('should only report the moves with dryRun', async () => {
+    const source = join(root, 'recording.txt');
+    await fs.writeFile(source, '09:00:00 Speaker 1\n', 'utf-8');
+
+    const result = await executeRename({
+      patterns: [source],
+      date: '2024-03-15',
+      outputDir: join(root, 'out'),
+      flat: false,
+      dryRun: true,
+    });
+
+    expect(result.moves).toEqual([{ source, target: join(root, 'out', '2024', '03', '15', '090000-090000.txt') }]);
+    expect(await fileExists(source)).toBe(true);
+  });
+});
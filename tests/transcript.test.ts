@@ -4,7 +4,7 @@
 
 import { describe, it, expect } from 'bun:test';
 import { TranscriptProcessor } from '../src/transcript.js';
-import { parse } from 'date-fns';
+import { parse, format } from 'date-fns';
 
 describe('TranscriptProcessor', () => {
   const baseTime = parse('18:01:12', 'HH:mm:ss', new Date(0));
@@ -23,6 +23,32 @@ Line without timestamp
     expect(result.body).toContain('18:01:15 Speaker 2');
   });
 
+  it('should only adjust the anchored leading timestamp and leave later ones verbatim', () => {
+    const input = '00:00:01 [00:00:01] note\n00:00:02 Speaker 1 (at 00:00:02): hi 00:00:03\n';
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+    expect(result.body).toBe('18:01:13 [00:00:01] note\n18:01:14 Speaker 1 (at 00:00:02): hi 00:00:03\n');
+  });
+
+  it('should adjust tab-delimited timestamps and keep the tab', () => {
+    const input = '00:00:01\tSpeaker 1: hi\n00:00:03\t00:00:05\tSpeaker 2: bye\n';
+
+    expect(TranscriptProcessor.adjust(input, baseTime, baseDate).body).toBe(
+      '18:01:13\tSpeaker 1: hi\n18:01:15\t00:00:05\tSpeaker 2: bye\n'
+    );
+    expect(TranscriptProcessor.adjust(input, baseTime, baseDate, { dualTimestamps: true }).body).toBe(
+      '18:01:13\tSpeaker 1: hi\n18:01:15\t18:01:17\tSpeaker 2: bye\n'
+    );
+  });
+
+  it('should adjust indented timestamps and keep the indent', () => {
+    const input = '  00:00:01 Speaker 1\n\t00:00:02 Speaker 2\n \t00:00:03 Speaker 1\n';
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+    expect(result.body).toBe('  18:01:13 Speaker 1\n\t18:01:14 Speaker 2\n \t18:01:15 Speaker 1\n');
+    expect(result.firstTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 13));
+  });
+
   it('should throw error when no timestamp lines exist', () => {
     const input = 'No timestamps here\n';
 
@@ -31,6 +57,15 @@ Line without timestamp
     }).toThrow('No timestamped lines were found');
   });
 
+  it('should throw error when fewer timestamp lines than required exist', () => {
+    const input = 'Meeting notes\n00:00:01 looks like a timestamp\nMore notes\nEven more\n';
+
+    expect(() => {
+      TranscriptProcessor.adjust(input, baseTime, baseDate, { minTimestamps: 5 });
+    }).toThrow('Only 1 timestamped line(s) found, expected at least 5');
+    expect(TranscriptProcessor.adjust(input, baseTime, baseDate).body).toContain('18:01:13 looks like');
+  });
+
   it('should preserve trailing newline presence', () => {
     const inputWithoutNewline = '00:00:01 Foo';
     const resultWithoutNewline = TranscriptProcessor.adjust(inputWithoutNewline, baseTime, baseDate);
@@ -41,6 +76,34 @@ Line without timestamp
     expect(resultWithNewline.body.endsWith('\n')).toBe(true);
   });
 
+  it('should pass skipped header lines through and take the span from the rest', () => {
+    const input = 'Exported 00:00:00 by recorder\n00:00:00 header\n00:00:05 Speaker 1\n00:00:09 Speaker 2\n';
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { skipLines: 2 });
+
+    expect(result.body).toBe('Exported 00:00:00 by recorder\n00:00:00 header\n18:01:17 Speaker 1\n18:01:21 Speaker 2\n');
+    expect(result.firstTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 17));
+    expect(result.lastTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 21));
+  });
+
+  it('should reject a line longer than maxLineBytes, counting UTF-8 bytes', () => {
+    const input = `00:00:01 Speaker 1\n00:00:02 ${'é'.repeat(10)}\n`;
+
+    expect(() => TranscriptProcessor.adjust(input, baseTime, baseDate, { maxLineBytes: 20 })).toThrow(
+      'Line 2 is 29 bytes, over the 20 byte line limit'
+    );
+    expect(TranscriptProcessor.adjust(input, baseTime, baseDate, { maxLineBytes: 29 }).lastTimestamp).toEqual(
+      new Date(2024, 11, 25, 18, 1, 14)
+    );
+  });
+
+  it('should pass comment lines through verbatim with a comment prefix', () => {
+    const input = '// 00:00:01 not a real cue\n00:00:02 Speaker 1\n';
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { commentPrefix: '//' });
+
+    expect(result.body).toBe('// 00:00:01 not a real cue\n18:01:14 Speaker 1\n');
+    expect(result.firstTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 14));
+  });
+
   it('should detect out-of-order timestamps', () => {
     const input = '00:00:05 Later\n00:00:02 Earlier\n';
     const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
@@ -48,10 +111,228 @@ Line without timestamp
     expect(result.hasOutOfOrderTimestamps).toBe(true);
   });
 
+  it('should track tied timestamps separately from out-of-order ones', () => {
+    const result = TranscriptProcessor.adjust('00:00:01 A\n00:00:01 B\n00:00:02 C\n', baseTime, baseDate);
+
+    expect(result.hasTiedTimestamps).toBe(true);
+    expect(result.hasOutOfOrderTimestamps).toBe(false);
+  });
+
+  it('should tolerate backward jitter up to the out-of-order tolerance', () => {
+    const options = { outOfOrderTolerance: 2000 };
+    const atBoundary = TranscriptProcessor.adjust('00:00:10 A\n00:00:08 B\n', baseTime, baseDate, options);
+    const beyond = TranscriptProcessor.adjust('00:00:10 A\n00:00:07 B\n', baseTime, baseDate, options);
+
+    expect(atBoundary.hasOutOfOrderTimestamps).toBe(false);
+    expect(beyond.hasOutOfOrderTimestamps).toBe(true);
+    expect(TranscriptProcessor.findOutOfOrder('00:00:10 A\n00:00:08 B\n00:00:05 C\n', options)).toEqual([
+      { lineNumber: 3, timestamp: '00:00:05', text: 'C' },
+    ]);
+  });
+
   it('should not flag in-order timestamps', () => {
     const input = '00:00:01 First\n00:00:03 Second\n00:00:05 Third\n';
     const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
 
     expect(result.hasOutOfOrderTimestamps).toBe(false);
   });
+
+  it('should handle a multi-megabyte single line in linear time', () => {
+    const text = 'x'.repeat(8 * 1024 * 1024);
+    const input = `00:00:01 ${text}`;
+
+    const started = performance.now();
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+    const elapsed = performance.now() - started;
+
+    expect(result.body.length).toBe(input.length);
+    expect(result.body.startsWith('18:01:13 xxx')).toBe(true);
+    expect(result.body.endsWith('xxx')).toBe(true);
+    expect(elapsed).toBeLessThan(5000);
+  });
+
+  it('should produce the same body as line-by-line joining for many lines', () => {
+    const lines = Array.from({ length: 20000 }, (_, i) => (i % 3 === 0 ? '00:00:01 Speaker' : `text ${i}`));
+    const input = lines.join('\n') + '\n';
+
+    const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+    expect(result.body).toBe(input.replaceAll('00:00:01 Speaker', '18:01:13 Speaker'));
+  });
+
+  describe('listing', () => {
+    it('should list detected timestamps with line numbers and count plain lines', () => {
+      const input = 'Meeting notes\n00:00:01 Speaker 1: Hello\ncontinued\n00:00:05 Speaker 2: Hi\n';
+
+      expect(TranscriptProcessor.list(input)).toEqual({
+        timestamps: [
+          { lineNumber: 2, timestamp: '00:00:01', text: 'Speaker 1: Hello' },
+          { lineNumber: 4, timestamp: '00:00:05', text: 'Speaker 2: Hi' },
+        ],
+        plainLines: 2,
+      });
+    });
+  });
+
+  describe('order check', () => {
+    it('should report nothing for timestamps in order', () => {
+      expect(TranscriptProcessor.findOutOfOrder('00:00:01 A\nnote\n00:00:01 B\n00:00:09 C\n')).toEqual([]);
+    });
+
+    it('should report each line earlier than the one before it', () => {
+      expect(TranscriptProcessor.findOutOfOrder('00:00:05 A\n00:00:03 B\n00:00:09 C\n00:00:08 D\n')).toEqual([
+        { lineNumber: 2, timestamp: '00:00:03', text: 'B' },
+        { lineNumber: 4, timestamp: '00:00:08', text: 'D' },
+      ]);
+    });
+  });
+
+  describe('dual timestamps', () => {
+    it('should adjust both leading timestamps and keep single-timestamp lines working', () => {
+      const input = '00:00:01 00:00:04 Speaker 1: hi\n00:00:05 Speaker 2: single\n00:00:06\t00:00:09 Speaker 1: tab\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { dualTimestamps: true });
+
+      expect(result.body).toBe(
+        '18:01:13 18:01:16 Speaker 1: hi\n18:01:17 Speaker 2: single\n18:01:18\t18:01:21 Speaker 1: tab\n'
+      );
+      expect(format(result.lastTimestamp, 'HH:mm:ss')).toBe('18:01:21');
+    });
+
+    it('should only adjust the first timestamp when dual mode is off', () => {
+      const input = '00:00:01 00:00:04 Speaker 1: hi\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe('18:01:13 00:00:04 Speaker 1: hi\n');
+    });
+  });
+
+  describe('date-prefixed lines', () => {
+    it('should ignore the per-line date and rewrite it from the adjusted timestamp', () => {
+      const input = '2025-01-27 00:00:01 Speaker 1\nHello\n2025-01-27 06:00:00 Speaker 2\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe('2024-12-25 18:01:13 Speaker 1\nHello\n2024-12-26 00:01:12 Speaker 2\n');
+      expect(result.lastTimestamp.getDate()).toBe(26);
+    });
+
+    it('should roll the date forward across several midnights for hours past 24', () => {
+      const input = '2025-01-27 00:00:01 Start\n2025-01-27 25:30:00 Next day\n2025-01-27 48:00:00 Two days on\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe(
+        '2024-12-25 18:01:13 Start\n2024-12-26 19:31:12 Next day\n2024-12-27 18:01:12 Two days on\n'
+      );
+      expect(result.lastTimestamp).toEqual(new Date(2024, 11, 27, 18, 1, 12));
+    });
+
+    it('should parse hours past 99 with a wider hour field', () => {
+      const input = '2025-01-27 00:00:01 Start\n2025-01-27 100:00:00 Four days on\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe('2024-12-25 18:01:13 Start\n2024-12-29 22:01:12 Four days on\n');
+      expect(result.lastTimestamp).toEqual(new Date(2024, 11, 29, 22, 1, 12));
+    });
+
+    it('should not treat a bare date line as timestamped', () => {
+      const input = '2025-01-27 notes\n00:00:01 Speaker 1\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe('2025-01-27 notes\n18:01:13 Speaker 1\n');
+    });
+  });
+
+  describe('collapsing speakers', () => {
+    it('should collapse consecutive lines from the same speaker into one', () => {
+      const input =
+        '00:00:01 Speaker 1: Hello\n00:00:02 Speaker 1: there\n00:00:03 Speaker 1: friend\n00:00:04 Speaker 2: Hi\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { collapseSpeakers: true });
+
+      expect(result.body).toBe('18:01:13 Speaker 1: Hello there friend\n18:01:16 Speaker 2: Hi\n');
+      expect(format(result.lastTimestamp, 'HH:mm:ss')).toBe('18:01:16');
+    });
+
+    it('should keep continuation lines when collapsing header-style lines', () => {
+      const input = '00:00:01 Speaker 1\nHello\n00:00:02 Speaker 1\nthere\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { collapseSpeakers: true });
+
+      expect(result.body).toBe('18:01:13 Speaker 1\nHello\nthere\n');
+    });
+  });
+
+  describe('speaker case', () => {
+    const input = '00:00:01 speaker 1: hello World\n00:00:02 SPEAKER 2\n00:00:03 no label here\n';
+
+    it('should title-case speaker labels and leave the rest of the line alone', () => {
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { speakerCase: 'title' });
+
+      expect(result.body).toBe('18:01:13 Speaker 1: hello World\n18:01:14 Speaker 2\n18:01:15 no label here\n');
+    });
+
+    it('should upper-case speaker labels', () => {
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { speakerCase: 'upper' });
+
+      expect(result.body).toBe('18:01:13 SPEAKER 1: hello World\n18:01:14 SPEAKER 2\n18:01:15 no label here\n');
+    });
+
+    it('should lower-case speaker labels', () => {
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { speakerCase: 'lower' });
+
+      expect(result.body).toBe('18:01:13 speaker 1: hello World\n18:01:14 speaker 2\n18:01:15 no label here\n');
+    });
+  });
+
+  describe('original-time annotation', () => {
+    it('should annotate each adjusted line with its original timestamp', () => {
+      const input = '00:00:01 Speaker 1\nHello\n00:00:03 Speaker 2\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { annotateOriginal: true });
+
+      expect(result.body).toBe('18:01:13 (00:00:01) Speaker 1\nHello\n18:01:15 (00:00:03) Speaker 2\n');
+    });
+
+    it('should replace an existing annotation instead of stacking it', () => {
+      const input = '00:00:02 (00:00:01) Speaker 1\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { annotateOriginal: true });
+
+      expect(result.body).toBe('18:01:14 (00:00:02) Speaker 1\n');
+    });
+
+    it('should keep annotations verbatim when not annotating', () => {
+      const input = '00:00:02 (00:00:01) Speaker 1\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate);
+
+      expect(result.body).toBe('18:01:14 (00:00:01) Speaker 1\n');
+    });
+  });
+
+  describe('frame-based timestamps', () => {
+    it('should adjust HH:MM:SS:FF timestamps at 25 fps and roll frames into the next second', () => {
+      const input = '00:00:01:12 Speaker 1\n00:00:01:24 Speaker 2\n00:00:02:00 Speaker 1\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { fps: 25 });
+
+      expect(result.body).toBe('18:01:13:12 Speaker 1\n18:01:13:24 Speaker 2\n18:01:14:00 Speaker 1\n');
+      expect(result.hasOutOfOrderTimestamps).toBe(false);
+    });
+
+    it('should adjust HH:MM:SS:FF timestamps at 30 fps across a minute boundary', () => {
+      const input = '00:00:47:29 Speaker 1\n00:00:48:00 Speaker 2\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { fps: 30 });
+
+      expect(result.body).toBe('18:01:59:29 Speaker 1\n18:02:00:00 Speaker 2\n');
+      expect(result.lastTimestamp.getSeconds()).toBe(0);
+    });
+
+    it('should round to the nearest second with midpoints rounding up', () => {
+      const input = '00:00:01:04 Down\n00:00:01:06 Up\n00:00:01:05 Midpoint\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { fps: 10, round: 1000 });
+
+      expect(result.body).toBe('18:01:13:00 Down\n18:01:14:00 Up\n18:01:14:00 Midpoint\n');
+    });
+
+    it('should leave frame groups outside the frame rate untouched', () => {
+      const input = '00:00:01:25 Not a frame\n00:00:02:00 Speaker 1\n';
+      const result = TranscriptProcessor.adjust(input, baseTime, baseDate, { fps: 25 });
+
+      expect(result.body).toBe('00:00:01:25 Not a frame\n18:01:14:00 Speaker 1\n');
+    });
+  });
 });
@@ -0,0 +1,556 @@
+/**
+ * Tests for the update command.
+ */
+
+import { describe, it, expect, beforeEach, afterEach, spyOn } from 'bun:test';
+import { execFileSync } from 'child_process';
+import { promises as fs } from 'fs';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { createHash } from 'crypto';
+import {
+  executeUpdate,
+  executeUpdateTarget,
+  writeIndex,
+  nestedOutputName,
+  flatOutputName,
+  countTimestamps,
+  listSpeakers,
+  batchExitCode,
+} from '../src/commands/update.js';
+import { UpdateArgs } from '../src/types.js';
+import { fileExists } from '../src/utils/validation.js';
+import { warningExitCode } from '../src/warnings.js';
+
+describe('executeUpdate', () => {
+  let root: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(join(tmpdir(), 'plaud-tm-update-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  /**
+   * Writes an input transcript and returns update arguments targeting the temp root.
+   */
+  async function setup(contents: string, overrides: Partial<UpdateArgs> = {}): Promise<UpdateArgs> {
+    const file = join(root, 'input.txt');
+    await fs.writeFile(file, contents, 'utf-8');
+    return {
+      file,
+      outputDir: join(root, 'output'),
+      flat: false,
+      time: '18:01:12',
+      date: '2024-12-25',
+      ...overrides,
+    };
+  }
+
+  it('should write the adjusted transcript to the nested layout', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n');
+
+    const result = await executeUpdate(args);
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180113-180115.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:13 Speaker 1\n18:01:15 Speaker 2\n');
+    expect(result.warnings).toEqual([]);
+  });
+
+  it('should report out-of-order timestamps as a structured warning', async () => {
+    const args = await setup('00:00:05 Later\n00:00:02 Earlier\n');
+
+    const result = await executeUpdate(args);
+
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['outOfOrderTimestamps']);
+  });
+
+  it('should trace each timestamp mapping to stderr with --trace', async () => {
+    const args = await setup('Header\n00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { trace: true });
+    const stderr = spyOn(process.stderr, 'write').mockImplementation(() => true);
+    try {
+      const result = await executeUpdate(args);
+
+      expect(stderr.mock.calls.map(([chunk]) => String(chunk))).toEqual([
+        `${args.file}:2: 00:00:01 -> 2024-12-25 18:01:13\n`,
+        `${args.file}:3: 00:00:03 -> 2024-12-25 18:01:15\n`,
+      ]);
+      expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('Header\n18:01:13 Speaker 1\n18:01:15 Speaker 2\n');
+      stderr.mockClear();
+
+      await executeUpdate({ ...args, since: '18:01:14' });
+      expect(stderr.mock.calls.map(([chunk]) => String(chunk))).toEqual([
+        `${args.file}:3: 00:00:03 -> 2024-12-25 18:01:15\n`,
+      ]);
+    } finally {
+      stderr.mockRestore();
+    }
+  });
+
+  it('should write one JSON object per line with --format jsonl', async () => {
+    const args = await setup('Meeting notes\n00:00:01 Speaker 1: Hello\n00:00:03 Speaker 2: Hi\n', { format: 'jsonl' });
+
+    const result = await executeUpdate(args);
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180113-180115.jsonl'));
+    const lines = (await fs.readFile(result.outputPath, 'utf-8')).trimEnd().split('\n');
+    expect(lines.map((line) => JSON.parse(line))).toEqual([
+      { absolute: null, text: 'Meeting notes', had_timestamp: false },
+      { absolute: '2024-12-25T18:01:13', text: 'Speaker 1: Hello', had_timestamp: true },
+      { absolute: '2024-12-25T18:01:15', text: 'Speaker 2: Hi', had_timestamp: true },
+    ]);
+  });
+
+  it('should take JSON Lines text from the adjusted lines after --trim and --skip-lines', async () => {
+    const contents = '\nHeader 00:00:09\n00:00:01 Speaker 1: Hello\nmore\n00:00:03 Speaker 2: Bye\n\n';
+    const args = await setup(contents, { format: 'jsonl', trim: true, skipLines: 2 });
+
+    const result = await executeUpdate(args);
+
+    const lines = (await fs.readFile(result.outputPath, 'utf-8')).trimEnd().split('\n');
+    expect(lines.map((line) => JSON.parse(line))).toEqual([
+      { absolute: null, text: 'Header 00:00:09', had_timestamp: false },
+      { absolute: '2024-12-25T18:01:13', text: 'Speaker 1: Hello', had_timestamp: true },
+      { absolute: null, text: 'more', had_timestamp: false },
+      { absolute: '2024-12-25T18:01:15', text: 'Speaker 2: Bye', had_timestamp: true },
+    ]);
+  });
+
+  it('should write a .sha256 sidecar that matches the output with --checksum', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', {
+      checksum: true,
+      outputEncoding: 'utf-16le',
+    });
+
+    const result = await executeUpdate(args);
+
+    expect(result.checksumPath).toBe(result.outputPath + '.sha256');
+    const hash = createHash('sha256').update(await fs.readFile(result.outputPath)).digest('hex');
+    expect(await fs.readFile(result.checksumPath!, 'utf-8')).toBe(`${hash}  180113-180115.txt\n`);
+  });
+
+  it('should set the output mtime to the last timestamp with --preserve-mtime', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { preserveMtime: true });
+
+    const result = await executeUpdate(args);
+
+    expect(result.lastTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 15));
+    expect((await fs.stat(result.outputPath)).mtime.getTime()).toBe(result.lastTimestamp.getTime());
+  });
+
+  it.skipIf(process.platform === 'win32')('should refuse --preserve-mtime for a named pipe output', async () => {
+    const dayDir = join(root, 'output', '2024', '12', '25');
+    await fs.mkdir(dayDir, { recursive: true });
+    execFileSync('mkfifo', [join(dayDir, 'out.fifo')]);
+    const args = await setup('00:00:01 Speaker 1\n', { name: 'out.fifo', preserveMtime: true });
+
+    await expect(executeUpdate(args)).rejects.toThrow('--preserve-mtime cannot be used with output');
+  });
+
+  it('should warn or fail when midnight overflow moves the output off --date with --verify-date', async () => {
+    const contents = '00:00:01 Speaker 1\n00:10:00 Speaker 2\n';
+
+    const unchecked = await executeUpdate(await setup(contents, { time: '23:55:00' }));
+    const warned = await executeUpdate(await setup(contents, { time: '23:55:00', verifyDate: 'warn' }));
+
+    expect(unchecked.warnings).toEqual([]);
+    expect(warned.outputPath).toBe(join(root, 'output', '2024', '12', '26', '235501-000500.txt'));
+    expect(warned.warnings.map((warning) => warning.kind)).toEqual(['dateMismatch']);
+    expect(warned.warnings[0].message).toBe('the output is dated 2024-12-26, not the supplied date 2024-12-25');
+    await expect(executeUpdate(await setup(contents, { time: '23:55:00', verifyDate: 'error' }))).rejects.toThrow(
+      'rejected by --verify-date error'
+    );
+  });
+
+  it('should exit nonzero on out-of-order timestamps only with --fail-on-warning', async () => {
+    const args = await setup('00:00:05 Later\n00:00:02 Earlier\n');
+
+    const result = await executeUpdate(args);
+
+    expect(warningExitCode(result.warnings, true)).not.toBe(0);
+    expect(warningExitCode(result.warnings, false)).toBe(0);
+    expect(warningExitCode([], true)).toBe(0);
+  });
+
+  it('should count the timestamped lines without writing for --count-only', async () => {
+    const args = await setup('header\n00:00:01 Speaker 1\nplain\n00:00:03 Speaker 2\n', { time: undefined });
+
+    expect(await countTimestamps(args)).toBe(2);
+    expect(await fileExists(join(root, 'output'))).toBe(false);
+    await expect(countTimestamps({ ...args, file: join(root, 'missing.txt') })).rejects.toThrow();
+  });
+
+  it('should list each speaker once in order of first appearance for --speakers-only', async () => {
+    const args = await setup(
+      'Meeting notes\n00:00:01 Speaker 2: Hi\n00:00:03 Alice: Hello\ncontinued: not a label\n' +
+        '00:00:05 Speaker 2: Again\n00:00:07 Speaker 1\n00:00:09 Alice: Bye\n',
+      { time: undefined, date: undefined }
+    );
+
+    expect(await listSpeakers(args)).toEqual(['Speaker 2', 'Alice', 'Speaker 1']);
+    expect(await fileExists(join(root, 'output'))).toBe(false);
+  });
+
+  it('should decode a UTF-16LE input and write UTF-8 unless asked for UTF-16LE', async () => {
+    const args = await setup('');
+    await fs.writeFile(args.file, Buffer.from('\uFEFF00:00:01 Speaker 1: Grüße\n00:00:03 Speaker 2\n', 'utf16le'));
+
+    const result = await executeUpdate(args);
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:13 Speaker 1: Grüße\n18:01:15 Speaker 2\n');
+
+    const utf16 = await executeUpdate({ ...args, outputEncoding: 'utf-16le' });
+    expect(await fs.readFile(utf16.outputPath)).toEqual(
+      Buffer.from('\uFEFF18:01:13 Speaker 1: Grüße\n18:01:15 Speaker 2\n', 'utf16le')
+    );
+  });
+
+  it('should fail without creating a missing --output-dir under --no-create-dirs', async () => {
+    const outputDir = join(root, 'missing', 'out');
+    const args = await setup('00:00:01 Speaker 1\n', { outputDir, noCreateDirs: true });
+
+    await expect(executeUpdate(args)).rejects.toThrow(`Output directory does not exist: ${outputDir}`);
+    expect(await fileExists(join(root, 'missing'))).toBe(false);
+  });
+
+  it('should refuse to write into a date directory with transcripts under --exclusive-dir', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { exclusiveDir: true });
+    const dayDir = join(root, 'output', '2024', '12', '25');
+    await fs.mkdir(dayDir, { recursive: true });
+    await fs.writeFile(join(dayDir, '170000-171000.txt'), '17:00:00 Earlier\n', 'utf-8');
+
+    await expect(executeUpdate(args)).rejects.toThrow('already contains transcripts (170000-171000.txt)');
+    expect(await fs.readdir(dayDir)).toEqual(['170000-171000.txt']);
+  });
+
+  it('should use --name as the filename inside the date directories', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { name: 'meeting.txt' });
+
+    const result = await executeUpdate(args);
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', 'meeting.txt'));
+    await expect(executeUpdate({ ...args, name: join('sub', 'meeting.txt') })).rejects.toThrow('Invalid name');
+  });
+
+  it('should warn when the only timestamp is on the last line', async () => {
+    const args = await setup('Speaker 1\nHello there\n00:00:07 Speaker 2: bye\n');
+
+    const result = await executeUpdate(args);
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180119-180119.txt'));
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['zeroLengthSpan']);
+  });
+
+  it('should append warnings with the input name and a timestamp to --log-file', async () => {
+    const logFile = join(root, 'warnings.log');
+    const args = await setup('00:00:05 Later\n00:00:02 Earlier\n', { logFile });
+
+    await executeUpdate(args);
+
+    const [time, file, kind, message] = (await fs.readFile(logFile, 'utf-8')).trimEnd().split('\t');
+    expect(Number.isNaN(Date.parse(time))).toBe(false);
+    expect(file).toBe(args.file);
+    expect(kind).toBe('outOfOrderTimestamps');
+    expect(message).toBe('timestamps in input were not in chronological order');
+  });
+
+  it('should leave already-absolute timestamps unchanged with --auto-base and no offset', async () => {
+    const contents = '2024-03-15 18:01:13 Speaker 1\n2024-03-15 18:01:15 Speaker 2\n';
+
+    const result = await executeUpdate(await setup(contents, { time: undefined, date: undefined, autoBase: true }));
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '03', '15', '180113-180115.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(contents);
+    await expect(executeUpdate(await setup(contents, { time: undefined, autoBase: true }))).rejects.toThrow(
+      'cannot be used together with --date'
+    );
+  });
+
+  it('should take the date from a flat-format filename with --auto-date, overriding --date', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { time: '18:06:13', autoDate: true });
+    const file = join(root, '20240315_180613_181530.txt');
+    await fs.rename(args.file, file);
+
+    const result = await executeUpdate({ ...args, file });
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '03', '15', '180614-180616.txt'));
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['dateOverridden']);
+    expect(result.warnings[0].message).toBe('used 2024-03-15 from the file name instead of --date 2024-12-25');
+    await expect(executeUpdate({ ...args, file, date: undefined })).resolves.toMatchObject({ warnings: [] });
+    await expect(executeUpdate({ ...args, file, date: '2024-03-15' })).resolves.toMatchObject({ warnings: [] });
+    await expect(executeUpdate({ ...args, file, time: '2024-03-16 18:06:13' })).rejects.toThrow(
+      '--time with a date cannot be used together with --auto-date'
+    );
+    await expect(executeUpdate(args)).rejects.toThrow('Cannot infer a date');
+  });
+
+  it('should continue from the end of another transcript with --continue-from', async () => {
+    const first = await executeUpdate(await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n'));
+    const second = join(root, 'second.txt');
+    await fs.writeFile(second, '00:00:02 Speaker 1\n00:00:04 Speaker 2\n', 'utf-8');
+    const args = await setup('', { file: second, time: undefined, continueFrom: first.outputPath });
+
+    const result = await executeUpdate(args);
+
+    expect(result.firstTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 17));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:17 Speaker 1\n18:01:19 Speaker 2\n');
+    await fs.writeFile(second, 'no timestamps\n', 'utf-8');
+    await expect(executeUpdate({ ...args, file: first.outputPath, continueFrom: second })).rejects.toThrow(
+      'has no timestamped lines'
+    );
+  });
+
+  it('should shift already-absolute timestamps with --offset instead of rebasing', async () => {
+    const contents = '18:01:13 Speaker 1\n18:01:15 Speaker 2\n';
+
+    const shifted = await executeUpdate(await setup(contents, { time: undefined, offset: '00:03:00' }));
+    expect(await fs.readFile(shifted.outputPath, 'utf-8')).toBe('18:04:13 Speaker 1\n18:04:15 Speaker 2\n');
+    expect(shifted.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180413-180415.txt'));
+
+    const earlier = await executeUpdate(await setup(contents, { time: undefined, offset: '-00:00:13' }));
+    expect(await fs.readFile(earlier.outputPath, 'utf-8')).toBe('18:01:00 Speaker 1\n18:01:02 Speaker 2\n');
+
+    // The default rebase adds the start time to every timestamp instead
+    const rebased = await executeUpdate(await setup(contents, { time: '00:03:00' }));
+    expect(await fs.readFile(rebased.outputPath, 'utf-8')).toBe('18:04:13 Speaker 1\n18:04:15 Speaker 2\n');
+    const rebasedLate = await executeUpdate(await setup(contents, { time: '18:01:12' }));
+    expect(await fs.readFile(rebasedLate.outputPath, 'utf-8')).toBe('12:02:25 Speaker 1\n12:02:27 Speaker 2\n');
+  });
+
+  it('should reject --offset combined with --time', async () => {
+    const args = await setup('18:01:13 Speaker 1\n', { offset: '00:03:00' });
+
+    await expect(executeUpdate(args)).rejects.toThrow('--offset cannot be used together with --time');
+  });
+
+  it.skipIf(process.platform === 'win32' || process.getuid?.() === 0)(
+    'should fail early with a clear error when the output directory is not writable',
+    async () => {
+      const locked = join(root, 'locked');
+      await fs.mkdir(locked, { mode: 0o555 });
+      // No timestamps: a late failure would surface as a transcript error instead
+      const args = await setup('no timestamps here\n', { outputDir: join(locked, 'output') });
+
+      try {
+        await expect(executeUpdate(args)).rejects.toThrow(`Output directory '${join(locked, 'output')}' is not writable`);
+      } finally {
+        await fs.chmod(locked, 0o755);
+      }
+    }
+  );
+
+  it.skipIf(process.platform === 'win32' || process.getuid?.() === 0)(
+    'should probe the resolved date directory, not just the output root',
+    async () => {
+      const dayDir = join(root, 'output', '2024', '12', '25');
+      await fs.mkdir(dayDir, { recursive: true });
+      await fs.chmod(dayDir, 0o555);
+      const args = await setup('00:00:01 Speaker 1\n');
+
+      try {
+        await expect(executeUpdate(args)).rejects.toThrow(`Output directory '${dayDir}' is not writable`);
+      } finally {
+        await fs.chmod(dayDir, 0o755);
+      }
+    }
+  );
+
+  it('should take the date from a combined --time value', async () => {
+    const args = await setup('00:00:01 Speaker 1\n', { time: '2024-12-26 18:01:12', date: undefined });
+
+    const result = await executeUpdate(args);
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '26', '180113-180113.txt'));
+  });
+
+  it('should write an index listing the output and its span', async () => {
+    const index = join(root, 'index.txt');
+    const args = await setup('00:00:01 Speaker 1\n00:00:03 Speaker 2\n', { index });
+
+    const result = await executeUpdate(args);
+
+    expect(await fs.readFile(index, 'utf-8')).toBe(
+      `# plaud-tm index\n${result.outputPath}\t2024-12-25 18:01:13\t2024-12-25 18:01:15\n`
+    );
+  });
+
+  it('should return the first adjusted lines with --preview while still writing', async () => {
+    const args = await setup('00:00:01 Zoë: héllo 👋\n00:00:03 Speaker 2\n00:00:05 Speaker 1\n', { preview: 2 });
+
+    const result = await executeUpdate(args);
+
+    expect(result.preview).toEqual(['18:01:13 Zoë: héllo 👋', '18:01:15 Speaker 2']);
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toContain('18:01:17 Speaker 1');
+  });
+
+  it('should drop leading and trailing blank lines with --trim', async () => {
+    const args = await setup('\n  \n00:00:01 Speaker 1\n\n00:00:03 Speaker 2\n\n\n', { trim: true, timestampIndex: true });
+
+    const result = await executeUpdate(args);
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:13 Speaker 1\n\n18:01:15 Speaker 2\n');
+    expect(await fs.readFile(result.timestampIndexPath!, 'utf-8')).toBe('18:01:13\t1\n18:01:15\t3\n');
+  });
+
+  it('should reject an empty input unless --allow-empty is set', async () => {
+    const args = await setup('');
+
+    await expect(executeUpdate(args)).rejects.toThrow(`Input file is empty: ${args.file}`);
+
+    const result = await executeUpdate({ ...args, allowEmpty: true });
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180112-180112.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('');
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['emptyInput']);
+  });
+
+  it('should warn about lines starting with an invalid timestamp and keep them unchanged', async () => {
+    const args = await setup('00:00:01 Speaker 1\n00:61:00 Speaker 2\n00:00:03 Speaker 1\n');
+
+    const result = await executeUpdate(args);
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(
+      '18:01:13 Speaker 1\n00:61:00 Speaker 2\n18:01:15 Speaker 1\n'
+    );
+    expect(result.warnings.map((warning) => warning.kind)).toEqual(['malformedLines']);
+    expect(result.warnings[0].message).toContain('1 line(s) start with an invalid timestamp');
+  });
+
+  it('should write a timestamps-only .idx next to the output', async () => {
+    const args = await setup('00:00:01 Speaker 1\nHello\n00:00:03 Speaker 2\n', { timestampIndex: true });
+    const second = join(root, 'second.txt');
+    await fs.writeFile(second, '00:00:05 Speaker 1\n', 'utf-8');
+
+    const result = await executeUpdate({ ...args, mergeFiles: [second] });
+
+    expect(result.timestampIndexPath).toBe(join(root, 'output', '2024', '12', '25', '180113-180117.idx'));
+    expect(await fs.readFile(result.timestampIndexPath!, 'utf-8')).toBe('18:01:13\t1\n18:01:15\t3\n18:01:17\t4\n');
+  });
+
+  it('should list every outcome in processing order in the index', async () => {
+    const index = join(root, 'index.txt');
+    const later = await executeUpdate(await setup('00:00:01 Later\n', { time: '20:00:00' }));
+    const earlier = await executeUpdate(await setup('00:00:01 Earlier\n', { time: '08:00:00' }));
+
+    await writeIndex(index, [later, earlier]);
+
+    const lines = (await fs.readFile(index, 'utf-8')).trimEnd().split('\n');
+    expect(lines.slice(1).map((line) => line.split('\t')[0])).toEqual([later.outputPath, earlier.outputPath]);
+  });
+
+  it('should print a diff of the changed timestamp lines instead of writing with --diff', async () => {
+    const args = await setup('00:00:01 Speaker 1\nHello\n00:00:03 Speaker 2\n', { diff: true });
+
+    const result = await executeUpdate(args);
+
+    expect(result.diff).toContain('-00:00:01 Speaker 1');
+    expect(result.diff).toContain('+18:01:13 Speaker 1');
+    expect(result.diff).toContain('-00:00:03 Speaker 2');
+    expect(result.diff).toContain('+18:01:15 Speaker 2');
+    expect(result.diff).toContain(' Hello');
+    expect(await fileExists(result.outputPath)).toBe(false);
+  });
+
+  it('should update each flat-named transcript in a directory using the start from its name', async () => {
+    const inputDir = join(root, 'recordings');
+    await fs.mkdir(inputDir);
+    await fs.writeFile(join(inputDir, '20240315_180613_181530.txt'), '00:00:01 A\n00:00:05 B\n', 'utf-8');
+    await fs.writeFile(join(inputDir, '20240316_090000_091000.txt'), '00:00:10 C\n', 'utf-8');
+    await fs.writeFile(join(inputDir, 'notes.txt'), '00:00:01 D\n', 'utf-8');
+
+    const result = await executeUpdateTarget({ file: inputDir, outputDir: join(root, 'output'), flat: false });
+
+    expect(result.outcomes.map((outcome) => outcome.outputPath)).toEqual([
+      join(root, 'output', '2024', '03', '15', '180614-180618.txt'),
+      join(root, 'output', '2024', '03', '16', '090010-090010.txt'),
+    ]);
+    expect(await fs.readFile(result.outcomes[0].outputPath, 'utf-8')).toBe('18:06:14 A\n18:06:18 B\n');
+    expect(result.failures).toHaveLength(1);
+    expect(result.failures[0].file).toBe(join(inputDir, 'notes.txt'));
+    expect(result.failures[0].message).toContain('Cannot infer the start time');
+    expect(batchExitCode(result, false)).toBe(1);
+    expect(batchExitCode({ ...result, failures: [] }, false)).toBe(0);
+  });
+
+  it('should adjust and concatenate several inputs with --merge', async () => {
+    const args = await setup('00:00:01 A\n00:00:05 B\n');
+    const second = join(root, 'second.txt');
+    await fs.writeFile(second, '00:00:10 C', 'utf-8');
+
+    const shared = await executeUpdate({ ...args, mergeFiles: [second], diff: true });
+    expect(shared.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180113-180122.txt'));
+
+    const result = await executeUpdate({ ...args, mergeFiles: [second], mergeBase: 'chained' });
+
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180113-180127.txt'));
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:13 A\n18:01:17 B\n18:01:27 C');
+    expect(result.hasOutOfOrderTimestamps).toBe(false);
+  });
+
+  it('should allow, warn about, or reject tied timestamps per --ties', async () => {
+    const args = await setup('00:00:01 A\n00:00:01 B\n00:00:03 C\n');
+
+    const allowed = await executeUpdate(args);
+    expect(allowed.warnings).toEqual([]);
+
+    const warned = await executeUpdate({ ...args, ties: 'warn' });
+    expect(warned.warnings.map((warning) => warning.kind)).toEqual(['tiedTimestamps']);
+    expect(warned.hasOutOfOrderTimestamps).toBe(false);
+
+    await fs.rm(join(root, 'output'), { recursive: true });
+    await expect(executeUpdate({ ...args, ties: 'error' })).rejects.toThrow('share a timestamp');
+    expect(await fileExists(join(root, 'output'))).toBe(false);
+  });
+
+  const fiveLines = '00:00:01 A\nfirst\n00:00:10 B\nsecond\n00:00:20 C\n00:00:30 D\nfourth\n00:00:40 E\n';
+
+  it('should keep only the lines inside a --since/--until window', async () => {
+    const args = await setup(fiveLines, { since: '18:01:22', until: '18:01:42' });
+
+    const result = await executeUpdate(args);
+
+    // since is inclusive (18:01:22) and until exclusive (18:01:42)
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe('18:01:22 B\nsecond\n18:01:32 C\n');
+    expect(result.outputPath).toBe(join(root, 'output', '2024', '12', '25', '180122-180132.txt'));
+  });
+
+  it('should compare +HH:MM:SS bounds with the input timestamps', async () => {
+    const args = await setup(fiveLines, { since: '+00:00:10', until: '+00:00:40' });
+
+    const result = await executeUpdate(args);
+
+    expect(await fs.readFile(result.outputPath, 'utf-8')).toBe(
+      '18:01:22 B\nsecond\n18:01:32 C\n18:01:42 D\nfourth\n'
+    );
+    expect(result.firstTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 22));
+    expect(result.lastTimestamp).toEqual(new Date(2024, 11, 25, 18, 1, 42));
+  });
+});
+
+describe('output naming', () => {
+  const first = new Date(2024, 11, 25, 23, 59, 50);
+  const last = new Date(2024, 11, 26, 0, 0, 10);
+
+  it('should place nested output under the day the transcript ended', () => {
+    expect(nestedOutputName(first, last)).toBe(join('2024', '12', '26', '235950-000010.txt'));
+  });
+
+  it('should place iso-week output under the ISO week-numbering year', () => {
+    expect(nestedOutputName(first, last, 'iso-week')).toBe(join('2024', 'W52', '235950-000010.txt'));
+
+    const monday = new Date(2024, 11, 30, 9, 0, 0);
+    expect(nestedOutputName(monday, monday, 'iso-week')).toBe(join('2025', 'W01', '090000-090000.txt'));
+  });
+
+  it('should build flat names from the given date and span', () => {
+    expect(flatOutputName(last, first, last)).toBe('20241226_235950_000010.txt');
+    expect(flatOutputName(first, first, first)).toBe('20241225_235950_235950.txt');
+  });
+
+  it('should leave the end time out of flat names under the start policy', () => {
+    const start = new Date(2024, 11, 25, 18, 1, 13);
+    const end = new Date(2024, 11, 25, 18, 1, 15);
+
+    expect(flatOutputName(start, start, end, 'start')).toBe('20241225_180113.txt');
+  });
+});
@@ -0,0 +1,53 @@
+/**
+ * Tests for input validation helpers.
+ */
+
+import { describe, it, expect } from 'bun:test';
+import { format } from 'date-fns';
+import { validateTimeArgument, validateDate } from '../src/utils/validation.js';
+
+describe('validateTimeArgument', () => {
+  it('should parse a plain HH:MM:SS time without a date', () => {
+    const result = validateTimeArgument('18:01:12');
+
+    expect(format(result.time, 'HH:mm:ss')).toBe('18:01:12');
+    expect(result.date).toBeUndefined();
+  });
+
+  it('should parse a combined date and time separated by a space', () => {
+    const result = validateTimeArgument('2024-12-25 18:01:12');
+
+    expect(format(result.time, 'HH:mm:ss')).toBe('18:01:12');
+    expect(result.date && format(result.date, 'yyyy-MM-dd HH:mm:ss')).toBe('2024-12-25 00:00:00');
+  });
+
+  it('should parse a combined ISO date and time', () => {
+    const result = validateTimeArgument('2024-12-25T18:01:12');
+
+    expect(format(result.time, 'HH:mm:ss')).toBe('18:01:12');
+    expect(result.date && format(result.date, 'yyyy-MM-dd')).toBe('2024-12-25');
+  });
+
+  it('should reject an invalid combined value', () => {
+    expect(() => validateTimeArgument('2024-13-25 18:01:12')).toThrow('Invalid time');
+  });
+});
+
+describe('validateDate', () => {
+  it('should accept ISO and dotted dates without an input format', () => {
+    expect(format(validateDate('2024-12-25'), 'yyyy-MM-dd')).toBe('2024-12-25');
+    expect(format(validateDate('25.12.2024'), 'yyyy-MM-dd')).toBe('2024-12-25');
+  });
+
+  it('should read slashed dates in the given input format', () => {
+    expect(format(validateDate('12/25/2024', 'MM/DD/YYYY'), 'yyyy-MM-dd')).toBe('2024-12-25');
+    expect(format(validateDate('25/12/2024', 'DD/MM/YYYY'), 'yyyy-MM-dd')).toBe('2024-12-25');
+    expect(format(validateDate('2024-12-25', 'DD/MM/YYYY'), 'yyyy-MM-dd')).toBe('2024-12-25');
+  });
+
+  it('should reject slashed dates without an input format as ambiguous', () => {
+    expect(() => validateDate('12/25/2024')).toThrow("Ambiguous date '12/25/2024'");
+    expect(() => validateDate('25/12/2024', 'MM/DD/YYYY')).toThrow("Invalid date '25/12/2024'");
+    expect(() => validateDate('2024-12-25', 'YYYY/DD/MM')).toThrow("Invalid date input format 'YYYY/DD/MM'");
+  });
+});